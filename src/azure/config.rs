@@ -0,0 +1,47 @@
+//! Azure DevOps configuration
+use super::platform::AzurePlatform;
+use crate::{
+    config::GitMoverConfig, config_password_wrap, config_value_wrap, errors::GitMoverError,
+};
+use serde::{Deserialize, Serialize};
+
+/// Azure DevOps configuration
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct AzureConfig {
+    /// Azure DevOps organization
+    pub organization: Option<String>,
+
+    /// Azure DevOps project
+    pub project: Option<String>,
+
+    /// Azure DevOps personal access token
+    pub token: Option<String>,
+}
+
+impl AzureConfig {
+    /// Get the azure platform
+    pub fn get_plateform(config: &mut GitMoverConfig) -> Result<AzurePlatform, GitMoverError> {
+        let organization = config_value_wrap!(
+            config,
+            azure,
+            AzureConfig,
+            organization,
+            "your azure devops organization"
+        );
+        let project = config_value_wrap!(
+            config,
+            azure,
+            AzureConfig,
+            project,
+            "your azure devops project"
+        );
+        let token = config_password_wrap!(
+            config,
+            azure,
+            AzureConfig,
+            token,
+            "your azure devops personal access token (https://dev.azure.com/<organization>/_usersSettings/tokens)"
+        );
+        Ok(AzurePlatform::new(organization, project, token))
+    }
+}
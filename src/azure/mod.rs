@@ -0,0 +1,13 @@
+//! Azure DevOps API module
+pub(crate) mod config;
+pub(crate) mod platform;
+pub(crate) mod repo;
+
+/// Azure DevOps URL
+const AZURE_URL: &str = "dev.azure.com";
+
+/// Azure DevOps SSH URL
+const AZURE_SSH_URL: &str = "ssh.dev.azure.com";
+
+/// Azure DevOps API version
+const AZURE_API_VERSION: &str = "7.1";
@@ -0,0 +1,385 @@
+//! Azure DevOps platform implementation
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::StatusCode;
+use std::pin::Pin;
+use urlencoding::encode;
+
+use super::repo::{AzureProject, AzureRepo, AzureRepoList};
+use super::{AZURE_API_VERSION, AZURE_SSH_URL, AZURE_URL};
+
+use crate::errors::GitMoverError;
+use crate::platform::{Platform, PlatformType, RepoField};
+use crate::utils::{check_ssh_access, normalize_url, Repo};
+
+/// Azure DevOps platform
+#[derive(Default, Debug, Clone)]
+pub struct AzurePlatform {
+    /// Azure DevOps organization
+    organization: String,
+
+    /// Azure DevOps project
+    project: String,
+
+    /// Azure DevOps personal access token
+    token: String,
+
+    /// API url
+    api_url: String,
+
+    /// Reqwest client
+    client: reqwest::Client,
+}
+
+impl AzurePlatform {
+    /// Create a new Azure DevOps platform
+    pub fn new(organization: String, project: String, token: String) -> Self {
+        Self {
+            organization,
+            project,
+            token,
+            api_url: normalize_url(AZURE_URL),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Get the API url of the project repositories
+    fn repos_url(&self) -> String {
+        format!(
+            "{}/{}/{}/_apis/git/repositories",
+            self.api_url,
+            encode(&self.organization),
+            encode(&self.project)
+        )
+    }
+
+    /// Get the project, used to create repositories
+    async fn get_project(&self) -> Result<AzureProject, GitMoverError> {
+        let url = format!(
+            "{}/{}/_apis/projects/{}",
+            self.api_url,
+            encode(&self.organization),
+            encode(&self.project)
+        );
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth("", Some(&self.token))
+            .header(ACCEPT, "application/json")
+            .query(&[("api-version", AZURE_API_VERSION)])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let text = response.text().await?;
+            return Err(GitMoverError::new(format!(
+                "{text} for {}",
+                PlatformType::Azure
+            )));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Get the Azure repository, with its id
+    async fn get_azure_repo(&self, name: &str) -> Result<AzureRepo, GitMoverError> {
+        let url = format!("{}/{}", self.repos_url(), encode(name));
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth("", Some(&self.token))
+            .header(ACCEPT, "application/json")
+            .query(&[("api-version", AZURE_API_VERSION)])
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await?;
+            return Err(
+                GitMoverError::new(format!("{text} for {}", PlatformType::Azure))
+                    .with_status(status),
+            );
+        }
+        let text = response.text().await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+}
+
+impl Platform for AzurePlatform {
+    fn check_git_access(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url_ssh = self.get_ssh_url();
+        Box::pin(async move {
+            let (stdout, stderr) = check_ssh_access(&url_ssh).await?;
+            // Azure DevOps doesn't greet the user, it only refuses the shell once authenticated
+            let authenticated = "Shell access is not supported";
+            if stdout.contains(authenticated) || stderr.contains(authenticated) {
+                Ok(())
+            } else {
+                Err(GitMoverError::new(format!(
+                    "Cannot access to {url_ssh}: stdout={} stderr={} for {}",
+                    stdout.trim(),
+                    stderr.trim(),
+                    self.get_type()
+                )))
+            }
+        })
+    }
+
+    fn get_remote_url(&self) -> String {
+        AZURE_URL.to_string()
+    }
+
+    fn get_ssh_url(&self) -> String {
+        format!("git@{AZURE_SSH_URL}")
+    }
+
+    fn get_git_url(&self, repo_name: &str) -> String {
+        format!(
+            "{}:v3/{}/{}/{}",
+            self.get_ssh_url(),
+            encode(&self.organization),
+            encode(&self.project),
+            encode(repo_name)
+        )
+    }
+
+    fn get_username(&self) -> &str {
+        &self.organization
+    }
+
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Azure
+    }
+
+    fn get_repo_fields(&self) -> &'static [RepoField] {
+        // Azure repos don't have descriptions
+        &[RepoField::Private]
+    }
+
+    fn create_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = self.repos_url();
+        Box::pin(async move {
+            // the repository is pushed to its path
+            match self.get_azure_repo(&repo.path).await {
+                Ok(_) => return Ok(()),
+                // only a missing repository is created, other errors are not hidden
+                Err(e) if e.status() == Some(StatusCode::NOT_FOUND.as_u16()) => {}
+                Err(e) => return Err(e),
+            }
+            let project = self.get_project().await?;
+            if project.visibility == "public" && repo.private {
+                eprintln!(
+                    "Visibility is set by the project on {}: {} will be public",
+                    PlatformType::Azure,
+                    repo.path
+                );
+            }
+            let json_body = AzureRepo {
+                name: repo.path.to_string(),
+                project,
+                ..Default::default()
+            };
+            let request = client
+                .post(url)
+                .basic_auth("", Some(&self.token))
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .query(&[("api-version", AZURE_API_VERSION)])
+                .json(&json_body)
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    PlatformType::Azure
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    fn edit_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        Box::pin(async move {
+            // description and visibility can't be set on an Azure repository
+            self.get_azure_repo(&repo.path).await?;
+            Ok(())
+        })
+    }
+
+    fn get_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Repo, GitMoverError>> + Send + '_>> {
+        let name = name.to_string();
+        Box::pin(async move { Ok(self.get_azure_repo(&name).await?.into()) })
+    }
+
+    fn get_all_repos(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Repo>, GitMoverError>> + Send>> {
+        let token = self.token.clone();
+        let client = self.client.clone();
+        let url = self.repos_url();
+        Box::pin(async move {
+            let request = client
+                .get(&url)
+                .basic_auth("", Some(&token))
+                .header(ACCEPT, "application/json")
+                .query(&[("api-version", AZURE_API_VERSION)])
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    PlatformType::Azure
+                )));
+            }
+            let text = response.text().await?;
+            let repos: AzureRepoList = serde_json::from_str(&text)?;
+            let repos: Vec<Repo> = repos.value.into_iter().map(|r| r.into()).collect();
+            println!("Requested azure: {}", repos.len());
+            Ok(repos)
+        })
+    }
+
+    fn delete_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let name = name.to_string();
+        Box::pin(async move {
+            let azure_repo = self.get_azure_repo(&name).await?;
+            let url = format!("{}/{}", self.repos_url(), encode(&azure_repo.id));
+            let request = client
+                .delete(&url)
+                .basic_auth("", Some(&self.token))
+                .header(ACCEPT, "application/json")
+                .query(&[("api-version", AZURE_API_VERSION)])
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    PlatformType::Azure
+                )));
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn azure_git_url() {
+        let platform = AzurePlatform::new(
+            "org".to_string(),
+            "My Project".to_string(),
+            "token".to_string(),
+        );
+        assert_eq!(
+            platform.get_git_url("repo"),
+            "git@ssh.dev.azure.com:v3/org/My%20Project/repo"
+        );
+    }
+
+    #[tokio::test]
+    async fn azure_create_and_delete_repo() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/org/_apis/projects/proj"))
+            .and(query_param("api-version", AZURE_API_VERSION))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"id": "p1", "visibility": "private"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/org/proj/_apis/git/repositories/new"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // the repository is created with its path, not its display name
+        Mock::given(method("POST"))
+            .and(path("/org/proj/_apis/git/repositories"))
+            .and(body_partial_json(serde_json::json!({"name": "new"})))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/org/proj/_apis/git/repositories/old"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "r1", "name": "old", "project": {"id": "p1", "visibility": "private"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/org/proj/_apis/git/repositories/r1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let mut platform =
+            AzurePlatform::new("org".to_string(), "proj".to_string(), "token".to_string());
+        platform.api_url = server.uri();
+        let repo = Repo {
+            name: "New".to_string(),
+            path: "new".to_string(),
+            private: true,
+            ..Default::default()
+        };
+        platform.create_repo(repo).await?;
+        platform.delete_repo("old").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn azure_create_repo_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/org/proj/_apis/git/repositories/new"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/org/proj/_apis/git/repositories"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let mut platform =
+            AzurePlatform::new("org".to_string(), "proj".to_string(), "token".to_string());
+        platform.api_url = server.uri();
+        let repo = Repo {
+            name: "new".to_string(),
+            path: "new".to_string(),
+            ..Default::default()
+        };
+        // an unauthorized token is not mistaken for a missing repository
+        let result = platform.create_repo(repo).await;
+        assert!(result.is_err_and(|e| e.status() == Some(401)));
+    }
+}
@@ -0,0 +1,53 @@
+//! Azure DevOps Repo module
+use crate::utils::Repo;
+use serde::{Deserialize, Serialize};
+
+/// Azure DevOps Repo
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureRepo {
+    /// Repo id
+    #[serde(skip_serializing)]
+    pub id: String,
+
+    /// Repo name
+    pub name: String,
+
+    /// Project of the repo
+    pub project: AzureProject,
+
+    /// Whether the repo is a fork
+    #[serde(default, skip_serializing)]
+    pub is_fork: bool,
+}
+
+/// Azure DevOps Project
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct AzureProject {
+    /// Project id
+    pub id: String,
+
+    /// Project visibility
+    #[serde(default, skip_serializing)]
+    pub visibility: String,
+}
+
+/// Azure DevOps list of repositories
+#[derive(Deserialize, Debug, Clone)]
+pub struct AzureRepoList {
+    /// Repositories
+    pub value: Vec<AzureRepo>,
+}
+
+impl From<AzureRepo> for Repo {
+    fn from(repo: AzureRepo) -> Self {
+        Repo {
+            name: repo.name.clone(),
+            path: repo.name,
+            description: String::new(), // Azure repos don't have descriptions
+            private: repo.project.visibility != "public",
+            fork: repo.is_fork,
+            ..Default::default()
+        }
+    }
+}
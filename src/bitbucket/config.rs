@@ -0,0 +1,47 @@
+//! Bitbucket configuration
+use super::platform::BitbucketPlatform;
+use crate::{
+    config::GitMoverConfig, config_password_wrap, config_value_wrap, errors::GitMoverError,
+};
+use serde::{Deserialize, Serialize};
+
+/// Bitbucket configuration
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct BitbucketConfig {
+    /// Bitbucket username
+    pub username: Option<String>,
+
+    /// Bitbucket app password
+    pub app_password: Option<String>,
+
+    /// Bitbucket workspace (default to the username)
+    pub workspace: Option<String>,
+}
+
+impl BitbucketConfig {
+    /// Get the bitbucket platform
+    pub fn get_plateform(config: &mut GitMoverConfig) -> Result<BitbucketPlatform, GitMoverError> {
+        let username = config_value_wrap!(
+            config,
+            bitbucket,
+            BitbucketConfig,
+            username,
+            "your bitbucket username"
+        );
+        let app_password = config_password_wrap!(
+            config,
+            bitbucket,
+            BitbucketConfig,
+            app_password,
+            "your bitbucket app password (https://bitbucket.org/account/settings/app-passwords/)"
+        );
+        let workspace = match &config.config_data.bitbucket {
+            Some(bitbucket_config) => bitbucket_config
+                .workspace
+                .clone()
+                .filter(|workspace| !workspace.is_empty()),
+            None => None,
+        };
+        Ok(BitbucketPlatform::new(username, app_password, workspace))
+    }
+}
@@ -0,0 +1,325 @@
+//! Bitbucket Cloud platform implementation
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use std::pin::Pin;
+use urlencoding::encode;
+
+use super::repo::{BitbucketPage, BitbucketRepo};
+use super::{BITBUCKET_API_URL, BITBUCKET_URL};
+
+use crate::errors::GitMoverError;
+use crate::platform::{Platform, PlatformType};
+use crate::utils::Repo;
+
+/// Bitbucket Cloud platform
+#[derive(Default, Debug, Clone)]
+pub struct BitbucketPlatform {
+    /// Bitbucket username
+    username: String,
+
+    /// Bitbucket app password
+    app_password: String,
+
+    /// Bitbucket workspace
+    workspace: String,
+
+    /// API url
+    api_url: String,
+
+    /// Reqwest client
+    client: reqwest::Client,
+}
+
+impl BitbucketPlatform {
+    /// Create a new Bitbucket platform
+    pub fn new(username: String, app_password: String, workspace: Option<String>) -> Self {
+        let workspace = workspace.unwrap_or_else(|| username.clone());
+        Self {
+            username,
+            app_password,
+            workspace,
+            api_url: BITBUCKET_API_URL.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Get the API url of a repository
+    fn repo_url(&self, slug: &str) -> String {
+        format!(
+            "{}/repositories/{}/{}",
+            self.api_url,
+            encode(&self.workspace),
+            encode(&slugify(slug))
+        )
+    }
+}
+
+/// Get the Bitbucket slug of a repository name
+pub(crate) fn slugify(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+impl Platform for BitbucketPlatform {
+    fn get_remote_url(&self) -> String {
+        BITBUCKET_URL.to_string()
+    }
+
+    fn get_git_url(&self, repo_name: &str) -> String {
+        format!(
+            "{}:{}/{}.git",
+            self.get_ssh_url(),
+            self.workspace,
+            slugify(repo_name)
+        )
+    }
+
+    fn get_username(&self) -> &str {
+        &self.username
+    }
+
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Bitbucket
+    }
+
+    fn normalize_path(&self, path: &str) -> String {
+        slugify(path)
+    }
+
+    fn create_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = self.repo_url(&repo.path);
+        Box::pin(async move {
+            let json_body = BitbucketRepo {
+                name: repo.name.to_string(),
+                description: repo.description.to_string(),
+                is_private: repo.private,
+                scm: "git".to_string(),
+                ..Default::default()
+            };
+            let request = client
+                .post(url)
+                .basic_auth(&self.username, Some(&self.app_password))
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body)
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                let get_repo = match self.get_repo(repo.path.as_str()).await {
+                    Ok(repo) => repo,
+                    Err(e) => {
+                        return Err(GitMoverError::new(format!(
+                            "{text} for {}: {e}",
+                            PlatformType::Bitbucket
+                        )));
+                    }
+                };
+                if get_repo.description != repo.description || get_repo.private != repo.private {
+                    return self.edit_repo(repo).await;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn edit_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = self.repo_url(&repo.path);
+        Box::pin(async move {
+            let json_body = BitbucketRepo {
+                name: repo.name.to_string(),
+                description: repo.description.to_string(),
+                is_private: repo.private,
+                scm: "git".to_string(),
+                ..Default::default()
+            };
+            let request = client
+                .put(url)
+                .basic_auth(&self.username, Some(&self.app_password))
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body)
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    PlatformType::Bitbucket
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    fn get_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Repo, GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = self.repo_url(name);
+        Box::pin(async move {
+            let request = client
+                .get(&url)
+                .basic_auth(&self.username, Some(&self.app_password))
+                .header(ACCEPT, "application/json")
+                .send();
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    PlatformType::Bitbucket
+                )));
+            }
+            let text = response.text().await?;
+            let repo: BitbucketRepo = serde_json::from_str(&text)?;
+            Ok(repo.into())
+        })
+    }
+
+    fn get_all_repos(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Repo>, GitMoverError>> + Send>> {
+        let username = self.username.clone();
+        let app_password = self.app_password.clone();
+        let client = self.client.clone();
+        let first_url = format!(
+            "{}/repositories/{}?pagelen=100",
+            self.api_url,
+            encode(&self.workspace)
+        );
+        Box::pin(async move {
+            let mut next_url = Some(first_url);
+            let mut page: usize = 1;
+            let mut all_repos = vec![];
+            while let Some(url) = next_url {
+                let request = client
+                    .get(&url)
+                    .basic_auth(&username, Some(&app_password))
+                    .header(ACCEPT, "application/json")
+                    .send();
+
+                let response = request.await?;
+                if !response.status().is_success() {
+                    let text = response.text().await?;
+                    return Err(GitMoverError::new(format!(
+                        "{text} for {}",
+                        PlatformType::Bitbucket
+                    )));
+                }
+                let text = response.text().await?;
+                let bitbucket_page: BitbucketPage = serde_json::from_str(&text)?;
+                let repos: Vec<Repo> = bitbucket_page
+                    .values
+                    .into_iter()
+                    .map(|r| r.into())
+                    .collect();
+                println!("Requested bitbucket (page {}): {}", page, repos.len());
+                all_repos.extend(repos);
+                next_url = bitbucket_page.next;
+                page += 1;
+            }
+            Ok(all_repos)
+        })
+    }
+
+    fn delete_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = self.repo_url(name);
+        Box::pin(async move {
+            let request = client
+                .delete(&url)
+                .basic_auth(&self.username, Some(&self.app_password))
+                .header(ACCEPT, "application/json")
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    PlatformType::Bitbucket
+                )));
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn bitbucket_slug() {
+        assert_eq!(slugify("My Repo.rs"), "my-repo.rs");
+        let platform = BitbucketPlatform::new("user".to_string(), "pass".to_string(), None);
+        assert_eq!(
+            platform.get_git_url("My Repo"),
+            "git@bitbucket.org:user/my-repo.git"
+        );
+    }
+
+    #[tokio::test]
+    async fn bitbucket_pagination() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repositories/team"))
+            .and(query_param("pagelen", "100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "values": [
+                    {"name": "One", "slug": "one", "description": "", "is_private": true}
+                ],
+                "next": format!("{}/repositories/team?page=2", server.uri())
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repositories/team"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "values": [
+                    {"name": "Two", "slug": "two", "description": "fork", "is_private": false,
+                     "parent": {"full_name": "other/two"}}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let mut platform = BitbucketPlatform::new(
+            "user".to_string(),
+            "pass".to_string(),
+            Some("team".to_string()),
+        );
+        platform.api_url = server.uri();
+        let repos = platform.get_all_repos().await?;
+        assert_eq!(repos.len(), 2);
+        assert!(repos[0].private);
+        assert_eq!(repos[1].path, "two");
+        assert!(repos[1].fork);
+        Ok(())
+    }
+}
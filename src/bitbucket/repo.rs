@@ -0,0 +1,58 @@
+//! Bitbucket Repo module
+use crate::utils::Repo;
+use serde::{Deserialize, Serialize};
+
+/// Bitbucket Repo
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct BitbucketRepo {
+    /// Repo name
+    pub name: String,
+
+    /// Repo slug
+    #[serde(skip_serializing)]
+    pub slug: String,
+
+    /// Repo description
+    pub description: String,
+
+    /// Whether the repo is private
+    pub is_private: bool,
+
+    /// Parent repository, if the repo is a fork
+    #[serde(skip_serializing)]
+    pub parent: Option<BitbucketParent>,
+
+    /// Source control management (always `git`)
+    #[serde(skip_deserializing)]
+    pub scm: String,
+}
+
+/// Bitbucket parent repository
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct BitbucketParent {
+    /// Parent full name
+    pub full_name: String,
+}
+
+/// Bitbucket page of repositories
+#[derive(Deserialize, Debug, Clone)]
+pub struct BitbucketPage {
+    /// Repositories of the page
+    pub values: Vec<BitbucketRepo>,
+
+    /// Link to the next page
+    pub next: Option<String>,
+}
+
+impl From<BitbucketRepo> for Repo {
+    fn from(repo: BitbucketRepo) -> Self {
+        Repo {
+            name: repo.name,
+            path: repo.slug,
+            description: repo.description,
+            private: repo.is_private,
+            fork: repo.parent.is_some(),
+            ..Default::default()
+        }
+    }
+}
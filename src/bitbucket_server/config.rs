@@ -0,0 +1,72 @@
+//! Bitbucket Server configuration
+use super::platform::BitbucketServerPlatform;
+use crate::{
+    config::GitMoverConfig, config_password_wrap, config_value_wrap, errors::GitMoverError,
+};
+use serde::{Deserialize, Serialize};
+
+/// Bitbucket Server configuration
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct BitbucketServerConfig {
+    /// Bitbucket Server username
+    pub username: Option<String>,
+
+    /// Bitbucket Server HTTP access token
+    pub token: Option<String>,
+
+    /// Bitbucket Server url
+    pub url: Option<String>,
+
+    /// Bitbucket Server project key
+    pub project_key: Option<String>,
+
+    /// SSH port (default to 7999)
+    pub ssh_port: Option<u16>,
+}
+
+impl BitbucketServerConfig {
+    /// Get the bitbucket server platform
+    pub fn get_plateform(
+        config: &mut GitMoverConfig,
+    ) -> Result<BitbucketServerPlatform, GitMoverError> {
+        let url = config_value_wrap!(
+            config,
+            bitbucket_server,
+            BitbucketServerConfig,
+            url,
+            "your bitbucket server url (e.g. https://bitbucket.example.com)"
+        );
+        let project_key = config_value_wrap!(
+            config,
+            bitbucket_server,
+            BitbucketServerConfig,
+            project_key,
+            "your bitbucket server project key"
+        );
+        let username = config_value_wrap!(
+            config,
+            bitbucket_server,
+            BitbucketServerConfig,
+            username,
+            "your bitbucket server username"
+        );
+        let token = config_password_wrap!(
+            config,
+            bitbucket_server,
+            BitbucketServerConfig,
+            token,
+            "your bitbucket server HTTP access token"
+        );
+        let ssh_port = match &config.config_data.bitbucket_server {
+            Some(bitbucket_server_config) => bitbucket_server_config.ssh_port,
+            None => None,
+        };
+        Ok(BitbucketServerPlatform::new(
+            username,
+            token,
+            &url,
+            project_key,
+            ssh_port,
+        ))
+    }
+}
@@ -0,0 +1,7 @@
+//! Bitbucket Server (Data Center) API module
+pub(crate) mod config;
+pub(crate) mod platform;
+pub(crate) mod repo;
+
+/// Default Bitbucket Server SSH port
+const BITBUCKET_SERVER_SSH_PORT: u16 = 7999;
@@ -0,0 +1,361 @@
+//! Bitbucket Server platform implementation
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use std::pin::Pin;
+use urlencoding::encode;
+
+use super::repo::{BitbucketServerPage, BitbucketServerRepo};
+use super::BITBUCKET_SERVER_SSH_PORT;
+
+use crate::bitbucket::platform::slugify;
+use crate::errors::GitMoverError;
+use crate::platform::{Platform, PlatformType};
+use crate::utils::{get_host, normalize_url, Repo};
+
+/// Bitbucket Server platform
+#[derive(Default, Debug, Clone)]
+pub struct BitbucketServerPlatform {
+    /// Bitbucket Server username
+    username: String,
+
+    /// Bitbucket Server HTTP access token
+    token: String,
+
+    /// Instance url (with scheme)
+    url: String,
+
+    /// Project key
+    project_key: String,
+
+    /// SSH port
+    ssh_port: u16,
+
+    /// Reqwest client
+    client: reqwest::Client,
+}
+
+impl BitbucketServerPlatform {
+    /// Create a new Bitbucket Server platform
+    pub fn new(
+        username: String,
+        token: String,
+        url: &str,
+        project_key: String,
+        ssh_port: Option<u16>,
+    ) -> Self {
+        Self {
+            username,
+            token,
+            url: normalize_url(url),
+            project_key,
+            ssh_port: ssh_port.unwrap_or(BITBUCKET_SERVER_SSH_PORT),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Get the API url of the project repositories
+    fn repos_url(&self) -> String {
+        format!(
+            "{}/rest/api/1.0/projects/{}/repos",
+            self.url,
+            encode(&self.project_key)
+        )
+    }
+
+    /// Get the API url of a repository
+    fn repo_url(&self, slug: &str) -> String {
+        format!("{}/{}", self.repos_url(), encode(&slugify(slug)))
+    }
+}
+
+impl Platform for BitbucketServerPlatform {
+    fn check_git_access(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = format!(
+            "{}/rest/api/1.0/projects/{}",
+            self.url,
+            encode(&self.project_key)
+        );
+        Box::pin(async move {
+            let response = client
+                .get(&url)
+                .header(AUTHORIZATION, format!("Bearer {}", self.token))
+                .header(ACCEPT, "application/json")
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "Cannot access to project {}: {text} for {}",
+                    self.project_key,
+                    self.get_type()
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    fn get_remote_url(&self) -> String {
+        get_host(&self.url)
+    }
+
+    fn get_ssh_url(&self) -> String {
+        format!("ssh://git@{}:{}", self.get_remote_url(), self.ssh_port)
+    }
+
+    fn get_git_url(&self, repo_name: &str) -> String {
+        format!(
+            "{}/{}/{}.git",
+            self.get_ssh_url(),
+            self.project_key.to_lowercase(),
+            slugify(repo_name)
+        )
+    }
+
+    fn get_username(&self) -> &str {
+        &self.username
+    }
+
+    fn get_type(&self) -> PlatformType {
+        PlatformType::BitbucketServer
+    }
+
+    fn normalize_path(&self, path: &str) -> String {
+        slugify(path)
+    }
+
+    fn create_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = self.repos_url();
+        Box::pin(async move {
+            let json_body = BitbucketServerRepo {
+                name: repo.name.to_string(),
+                description: repo.description.to_string(),
+                public: !repo.private,
+                scm_id: "git".to_string(),
+                ..Default::default()
+            };
+            let request = client
+                .post(url)
+                .header(AUTHORIZATION, format!("Bearer {}", self.token))
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body)
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                let get_repo = match self.get_repo(repo.path.as_str()).await {
+                    Ok(repo) => repo,
+                    Err(e) => {
+                        return Err(GitMoverError::new(format!(
+                            "{text} for {}: {e}",
+                            PlatformType::BitbucketServer
+                        )));
+                    }
+                };
+                if get_repo.description != repo.description || get_repo.private != repo.private {
+                    return self.edit_repo(repo).await;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn edit_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = self.repo_url(&repo.path);
+        Box::pin(async move {
+            let json_body = BitbucketServerRepo {
+                name: repo.name.to_string(),
+                description: repo.description.to_string(),
+                public: !repo.private,
+                scm_id: "git".to_string(),
+                ..Default::default()
+            };
+            let request = client
+                .put(url)
+                .header(AUTHORIZATION, format!("Bearer {}", self.token))
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body)
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    PlatformType::BitbucketServer
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    fn get_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Repo, GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = self.repo_url(name);
+        Box::pin(async move {
+            let request = client
+                .get(&url)
+                .header(AUTHORIZATION, format!("Bearer {}", self.token))
+                .header(ACCEPT, "application/json")
+                .send();
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    PlatformType::BitbucketServer
+                )));
+            }
+            let text = response.text().await?;
+            let repo: BitbucketServerRepo = serde_json::from_str(&text)?;
+            Ok(repo.into())
+        })
+    }
+
+    fn get_all_repos(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Repo>, GitMoverError>> + Send>> {
+        let token = self.token.clone();
+        let client = self.client.clone();
+        let url = self.repos_url();
+        Box::pin(async move {
+            let mut start: usize = 0;
+            let mut page: usize = 1;
+            let mut all_repos = vec![];
+            loop {
+                let request = client
+                    .get(&url)
+                    .header(AUTHORIZATION, format!("Bearer {token}"))
+                    .header(ACCEPT, "application/json")
+                    .query(&[("start", &start.to_string()), ("limit", &"100".to_string())])
+                    .send();
+
+                let response = request.await?;
+                if !response.status().is_success() {
+                    let text = response.text().await?;
+                    return Err(GitMoverError::new(format!(
+                        "{text} for {}",
+                        PlatformType::BitbucketServer
+                    )));
+                }
+                let text = response.text().await?;
+                let server_page: BitbucketServerPage = serde_json::from_str(&text)?;
+                let repos: Vec<Repo> = server_page.values.into_iter().map(|r| r.into()).collect();
+                println!(
+                    "Requested bitbucket server (page {}): {}",
+                    page,
+                    repos.len()
+                );
+                all_repos.extend(repos);
+                match server_page.next_page_start {
+                    Some(next_start) if !server_page.is_last_page => start = next_start,
+                    _ => break,
+                }
+                page += 1;
+            }
+            Ok(all_repos)
+        })
+    }
+
+    fn delete_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let client = self.client.clone();
+        let url = self.repo_url(name);
+        Box::pin(async move {
+            let request = client
+                .delete(&url)
+                .header(AUTHORIZATION, format!("Bearer {}", self.token))
+                .header(ACCEPT, "application/json")
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    PlatformType::BitbucketServer
+                )));
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn bitbucket_server_git_url() {
+        let platform = BitbucketServerPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            "https://bitbucket.example.com/",
+            "PRJ".to_string(),
+            None,
+        );
+        assert_eq!(
+            platform.get_git_url("My Repo"),
+            "ssh://git@bitbucket.example.com:7999/prj/my-repo.git"
+        );
+    }
+
+    #[tokio::test]
+    async fn bitbucket_server_pagination() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/1.0/projects/PRJ/repos"))
+            .and(query_param("start", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "values": [{"name": "One", "slug": "one", "public": true}],
+                "isLastPage": false,
+                "nextPageStart": 25
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/1.0/projects/PRJ/repos"))
+            .and(query_param("start", "25"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "values": [{"name": "Two", "slug": "two", "description": "desc",
+                            "origin": {"slug": "two"}}],
+                "isLastPage": true
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = BitbucketServerPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            &server.uri(),
+            "PRJ".to_string(),
+            None,
+        );
+        let repos = platform.get_all_repos().await?;
+        assert_eq!(repos.len(), 2);
+        assert!(!repos[0].private);
+        assert!(repos[1].private);
+        assert!(repos[1].fork);
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+//! Bitbucket Server Repo module
+use crate::utils::Repo;
+use serde::{Deserialize, Serialize};
+
+/// Bitbucket Server Repo
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct BitbucketServerRepo {
+    /// Repo name
+    pub name: String,
+
+    /// Repo slug
+    #[serde(skip_serializing)]
+    pub slug: String,
+
+    /// Repo description
+    #[serde(default)]
+    pub description: String,
+
+    /// Whether the repo is public
+    #[serde(default)]
+    pub public: bool,
+
+    /// Origin repository, if the repo is a fork
+    #[serde(skip_serializing)]
+    pub origin: Option<BitbucketServerOrigin>,
+
+    /// Source control management id (always `git`)
+    #[serde(rename = "scmId", skip_deserializing)]
+    pub scm_id: String,
+}
+
+/// Bitbucket Server origin repository
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct BitbucketServerOrigin {
+    /// Origin slug
+    pub slug: String,
+}
+
+/// Bitbucket Server page of repositories
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketServerPage {
+    /// Repositories of the page
+    pub values: Vec<BitbucketServerRepo>,
+
+    /// Whether this is the last page
+    pub is_last_page: bool,
+
+    /// Start of the next page
+    pub next_page_start: Option<usize>,
+}
+
+impl From<BitbucketServerRepo> for Repo {
+    fn from(repo: BitbucketServerRepo) -> Self {
+        Repo {
+            name: repo.name,
+            path: repo.slug,
+            description: repo.description,
+            private: !repo.public,
+            fork: repo.origin.is_some(),
+            ..Default::default()
+        }
+    }
+}
@@ -2,18 +2,18 @@
 use crate::{
     config::GitMoverConfig, errors::GitMoverError, platform::PlatformType, utils::main_sync,
 };
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use serde::Deserialize;
 use std::path::PathBuf;
 
 /// git-mover - Move git repositories to a new location
 #[derive(Parser, Deserialize, Default, Clone, Debug)]
 pub struct GitMoverCli {
-    /// The source platform (github, gitlab, codeberg)
+    /// The source platform (github, github-enterprise, gitlab, codeberg, gitea, bitbucket, bitbucket-server, azure, sourcehut, local, ssh)
     #[arg(long, visible_alias = "from")]
     pub source: Option<PlatformType>,
 
-    /// The destination platform (github, gitlab, codeberg)
+    /// The destination platform (github, github-enterprise, gitlab, codeberg, gitea, bitbucket, bitbucket-server, azure, sourcehut, local, ssh)
     #[arg(long, visible_alias = "to")]
     pub destination: Option<PlatformType>,
 
@@ -41,12 +41,92 @@ pub struct GitMoverCli {
     #[arg(long)]
     pub manual: bool,
 
+    /// Never prompt: answer yes to the questions and fail if a required value is missing (requires --private)
+    #[arg(long, visible_alias = "non-interactive", conflicts_with = "manual")]
+    pub yes: bool,
+
+    /// What to do with the private repositories (asked for each one by default)
+    #[arg(long, value_name = "POLICY")]
+    pub private: Option<PrivatePolicy>,
+
+    /// What to do with the repositories missing on the source
+    /// (prompt by default, never in non-interactive mode)
+    #[arg(long, value_name = "POLICY")]
+    pub delete: Option<DeletePolicy>,
+
+    /// Also migrate issues, comments and labels
+    #[arg(long)]
+    pub issues: bool,
+
+    /// Also migrate pull requests (merge requests) and their comments
+    #[arg(long)]
+    pub pull_requests: bool,
+
+    /// Also migrate releases and their assets
+    #[arg(long)]
+    pub releases: bool,
+
+    /// Also migrate the wiki repository (a Github or Gitea wiki must have a first page)
+    #[arg(long)]
+    pub wiki: bool,
+
+    /// Glob of the references to push, replacing the defaults (refs/heads/*, refs/tags/*, refs/notes/*)
+    #[arg(long = "include-ref", value_name = "GLOB")]
+    pub include_refs: Vec<String>,
+
+    /// Glob of the references to skip
+    #[arg(long = "exclude-ref", value_name = "GLOB")]
+    pub exclude_refs: Vec<String>,
+
+    /// Directory keeping the mirrors between the runs, only fetching the changes
+    #[arg(long)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Print what would be synced, edited and deleted, without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
     /// Verbose mode
     #[arg(short, long, visible_short_alias = 'd', action = clap::ArgAction::Count)]
     pub verbose: u8,
 }
 
+/// Policy of the private repositories
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivatePolicy {
+    /// Sync the private repositories
+    Sync,
+
+    /// Skip the private repositories
+    Skip,
+}
+
+/// Policy of the repositories of the destination missing on the source
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeletePolicy {
+    /// Never delete them
+    Never,
+
+    /// Ask before deleting each one
+    Prompt,
+
+    /// Delete them without asking
+    Always,
+}
+
 impl GitMoverCli {
+    /// Policy of the deletions, from `--delete`, `--no-delete` and `--yes`
+    pub fn delete_policy(&self) -> DeletePolicy {
+        match self.delete {
+            _ if self.no_delete => DeletePolicy::Never,
+            Some(policy) => policy,
+            None if self.yes => DeletePolicy::Never,
+            None => DeletePolicy::Prompt,
+        }
+    }
+
     /// Run the git-mover tool with the provided command line options
     /// # Errors
     /// Errors if something happens
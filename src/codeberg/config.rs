@@ -1,7 +1,8 @@
 //! Codeberg configuration
-use super::platform::CodebergPlatform;
+use super::CODEBERG_URL;
 use crate::{
     config::GitMoverConfig, config_password_wrap, config_value_wrap, errors::GitMoverError,
+    gitea::platform::GiteaPlatform, platform::PlatformType,
 };
 use serde::{Deserialize, Serialize};
 
@@ -13,11 +14,14 @@ pub struct CodebergConfig {
 
     /// Codeberg token
     pub token: Option<String>,
+
+    /// Owner of the repositories, an organization (default to the username)
+    pub owner: Option<String>,
 }
 
 impl CodebergConfig {
-    /// Get the codeberg platform
-    pub fn get_plateform(config: &mut GitMoverConfig) -> Result<CodebergPlatform, GitMoverError> {
+    /// Get the codeberg platform, a preset of the gitea platform
+    pub fn get_plateform(config: &mut GitMoverConfig) -> Result<GiteaPlatform, GitMoverError> {
         let username = config_value_wrap!(
             config,
             codeberg,
@@ -32,6 +36,21 @@ impl CodebergConfig {
             token,
             "your codeberg token (https://codeberg.org/user/settings/applications)"
         );
-        Ok(CodebergPlatform::new(username, token))
+        let owner = match &config.config_data.codeberg {
+            Some(codeberg_config) => codeberg_config
+                .owner
+                .clone()
+                .filter(|owner| !owner.is_empty()),
+            None => None,
+        };
+        Ok(GiteaPlatform::new(
+            username,
+            token,
+            CODEBERG_URL,
+            None,
+            None,
+            PlatformType::Codeberg,
+        )
+        .with_owner(owner))
     }
 }
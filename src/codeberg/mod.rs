@@ -1,7 +1,5 @@
 //! Codeberg API module.
 pub(crate) mod config;
-pub(crate) mod platform;
-pub(crate) mod repo;
 
 /// Codeberg URL
 const CODEBERG_URL: &str = "codeberg.org";
@@ -9,8 +9,19 @@ use home::home_dir;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    cli::GitMoverCli, codeberg::config::CodebergConfig, errors::GitMoverError,
-    github::config::GithubConfig, gitlab::config::GitlabConfig,
+    azure::config::AzureConfig,
+    bitbucket::config::BitbucketConfig,
+    bitbucket_server::config::BitbucketServerConfig,
+    cli::GitMoverCli,
+    codeberg::config::CodebergConfig,
+    errors::GitMoverError,
+    gitea::config::GiteaConfig,
+    github::config::{GithubConfig, GithubEnterpriseConfig},
+    gitlab::config::GitlabConfig,
+    local::config::LocalConfig,
+    refs::RefPolicy,
+    sourcehut::config::SourcehutConfig,
+    ssh::config::SshConfig,
 };
 
 /// Configuration data
@@ -26,6 +37,7 @@ pub struct GitMoverConfig {
     pub cli_args: GitMoverCli,
 }
 
+/// Configuration data stored in the configuration file
 #[derive(Deserialize, Serialize, Default, Clone, Debug)]
 pub struct ConfigData {
     /// Gitlab configuration
@@ -34,8 +46,35 @@ pub struct ConfigData {
     /// Github configuration
     pub github: Option<GithubConfig>,
 
+    /// Github Enterprise Server configuration
+    pub github_enterprise: Option<GithubEnterpriseConfig>,
+
     /// Codeberg configuration
     pub codeberg: Option<CodebergConfig>,
+
+    /// Gitea configuration
+    pub gitea: Option<GiteaConfig>,
+
+    /// Bitbucket configuration
+    pub bitbucket: Option<BitbucketConfig>,
+
+    /// Bitbucket Server configuration
+    pub bitbucket_server: Option<BitbucketServerConfig>,
+
+    /// Azure DevOps configuration
+    pub azure: Option<AzureConfig>,
+
+    /// SourceHut configuration
+    pub sourcehut: Option<SourcehutConfig>,
+
+    /// Local configuration
+    pub local: Option<LocalConfig>,
+
+    /// SSH configuration
+    pub ssh: Option<SshConfig>,
+
+    /// Policy of the references to push
+    pub refs: Option<RefPolicy>,
 }
 
 impl GitMoverConfig {
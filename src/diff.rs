@@ -0,0 +1,476 @@
+//! Difference between the repositories of the source and the destination
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::utils::Repo;
+use crate::{
+    cli::PrivatePolicy,
+    errors::GitMoverError,
+    platform::{Platform, RepoField},
+    refs::RefPolicy,
+    sync::ls_remote,
+};
+
+/// Maximum number of repositories compared at the same time (each opening two connections)
+const MAX_CONCURRENT_COMPARISONS: usize = 8;
+
+/// What to do to bring the destination up to date with the source
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct RepoDiff {
+    /// Repositories missing on the destination or with different references
+    pub to_sync: Vec<Repo>,
+
+    /// Repositories with the same references but different metadata
+    pub to_edit: Vec<Repo>,
+
+    /// Repositories of the destination missing on the source
+    pub to_delete: Vec<Repo>,
+
+    /// Number of repositories already up to date
+    pub up_to_date: usize,
+
+    /// Changes of the repositories existing on both sides, by source path
+    pub changes: HashMap<String, RepoChanges>,
+}
+
+/// Changes of a repository existing on the source and the destination
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct RepoChanges {
+    /// References to push, `None` if they could not be listed
+    pub refs: Option<Vec<RefChange>>,
+
+    /// Names of the metadata fields to edit
+    pub metadata: Vec<String>,
+}
+
+/// Reference of the source missing or different on the destination
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RefChange {
+    /// Name of the reference
+    pub name: String,
+
+    /// Target on the destination, if it exists
+    pub old: Option<git2::Oid>,
+
+    /// Target on the source
+    pub new: git2::Oid,
+}
+
+/// Get the metadata fields of a source repository that differ from the destination one.
+/// Only the given fields (reported by both platforms) are compared,
+/// and the settings unknown on one side are ignored
+pub(crate) fn metadata_changes(
+    source: &Repo,
+    destination: &Repo,
+    fields: &[RepoField],
+) -> Vec<String> {
+    let known_differs =
+        |a: Option<bool>, b: Option<bool>| matches!((a, b), (Some(a), Some(b)) if a != b);
+    let mut changes: Vec<String> = fields
+        .iter()
+        .filter(|field| match field {
+            RepoField::Description => source.description != destination.description,
+            RepoField::Private => source.private != destination.private,
+            RepoField::Homepage => source.homepage != destination.homepage,
+            RepoField::Archived => source.archived != destination.archived,
+            RepoField::Topics => {
+                let mut source_topics = source.normalized_topics();
+                let mut destination_topics = destination.normalized_topics();
+                source_topics.sort();
+                destination_topics.sort();
+                source_topics != destination_topics
+            }
+        })
+        .map(|field| field.to_string())
+        .collect();
+    let toggles = [
+        ("issues", source.has_issues, destination.has_issues),
+        ("wiki", source.has_wiki, destination.has_wiki),
+        ("projects", source.has_projects, destination.has_projects),
+    ];
+    for (toggle, source_toggle, destination_toggle) in toggles {
+        if known_differs(source_toggle, destination_toggle) {
+            changes.push(toggle.to_string());
+        }
+    }
+    changes
+}
+
+/// Get the references of the source missing or different on the destination.
+/// The references of the destination missing on the source are ignored, as they are never pushed
+fn ref_changes(
+    source_url: &str,
+    destination_url: &str,
+    policy: &RefPolicy,
+) -> Result<Vec<RefChange>, GitMoverError> {
+    let destination_refs: HashMap<_, _> = ls_remote(destination_url)?.into_iter().collect();
+    Ok(ls_remote(source_url)?
+        .into_iter()
+        .filter(|(ref_name, _)| policy.allows(ref_name))
+        .filter_map(|(name, new)| {
+            let old = destination_refs.get(&name).copied();
+            (old != Some(new)).then_some(RefChange { name, old, new })
+        })
+        .collect())
+}
+
+/// Compare the repositories by identity (path), then their references and their metadata
+pub(crate) async fn diff_repos(
+    source_platform: Arc<Box<dyn Platform>>,
+    destination_platform: Arc<Box<dyn Platform>>,
+    source_repos: Vec<Repo>,
+    destination_repos: Vec<Repo>,
+    policy: RefPolicy,
+) -> RepoDiff {
+    // the destination may rename the repositories (e.g. Bitbucket slugs)
+    let destination_path = |repo: &Repo| destination_platform.normalize_path(&repo.flat_path());
+    let source_paths: HashSet<String> = source_repos.iter().map(destination_path).collect();
+    let mut diff = RepoDiff {
+        to_delete: destination_repos
+            .iter()
+            .filter(|repo| !source_paths.contains(&destination_platform.normalize_path(&repo.path)))
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+
+    let destination_fields = destination_platform.get_repo_fields();
+    let fields: Vec<RepoField> = source_platform
+        .get_repo_fields()
+        .iter()
+        .filter(|field| destination_fields.contains(field))
+        .copied()
+        .collect();
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_COMPARISONS));
+    let mut set = JoinSet::new();
+    for repo in source_repos {
+        let destination_path = destination_path(&repo);
+        let Some(destination_repo) = destination_repos.iter().find(|destination_repo| {
+            destination_platform.normalize_path(&destination_repo.path) == destination_path
+        }) else {
+            diff.to_sync.push(repo);
+            continue;
+        };
+        let metadata = metadata_changes(&repo, destination_repo, &fields);
+        let source_url = source_platform.get_git_url(&repo.path);
+        let destination_url = destination_platform.get_git_url(&destination_path);
+        let policy = policy.clone();
+        let semaphore = semaphore.clone();
+        set.spawn(async move {
+            // the semaphore is never closed
+            let _permit = semaphore.acquire_owned().await.ok();
+            // ls-remote is blocking, the comparisons run on the blocking threads
+            let refs = tokio::task::spawn_blocking(move || {
+                ref_changes(&source_url, &destination_url, &policy)
+            })
+            .await;
+            // an unreachable repository is synced again
+            let refs = refs.ok().and_then(|refs| refs.ok());
+            (repo, RepoChanges { refs, metadata })
+        });
+    }
+    for (repo, changes) in set.join_all().await {
+        match &changes.refs {
+            Some(refs) if refs.is_empty() => {
+                if changes.metadata.is_empty() {
+                    diff.up_to_date += 1;
+                } else {
+                    diff.to_edit.push(repo.clone());
+                }
+            }
+            _ => diff.to_sync.push(repo.clone()),
+        }
+        diff.changes.insert(repo.path, changes);
+    }
+    diff.to_sync.sort_by(|a, b| a.name.cmp(&b.name));
+    diff.to_edit.sort_by(|a, b| a.name.cmp(&b.name));
+    diff
+}
+
+/// Describe what a sync would do, without doing it
+pub(crate) fn format_plan(
+    changes: &HashMap<String, RepoChanges>,
+    to_sync: &[Repo],
+    forks: Option<&[Repo]>,
+    to_edit: &[Repo],
+    to_delete: Option<&[Repo]>,
+    private: Option<PrivatePolicy>,
+) -> String {
+    let mut lines = vec!["Dry run: nothing will be changed".to_string()];
+    let mut plan_sync = |title: &str, repos: &[Repo]| {
+        let (skipped, repos): (Vec<&Repo>, Vec<&Repo>) = repos
+            .iter()
+            .partition(|repo| repo.private && private == Some(PrivatePolicy::Skip));
+        let (to_update, to_create): (Vec<&Repo>, Vec<&Repo>) = repos
+            .into_iter()
+            .partition(|repo| changes.contains_key(&repo.path));
+        // without policy, each private repository is asked before syncing it
+        let asked = |repo: &Repo| match (repo.private, private) {
+            (true, None) => " (private, asked)",
+            _ => "",
+        };
+        lines.push(format!("{title} to create ({}):", to_create.len()));
+        for repo in to_create {
+            lines.push(format!("  + {}{}", repo.show_full_name(), asked(repo)));
+        }
+        lines.push(format!("{title} to update ({}):", to_update.len()));
+        for repo in to_update {
+            lines.push(format!("  ~ {}{}", repo.show_full_name(), asked(repo)));
+            match changes.get(&repo.path).and_then(|c| c.refs.as_ref()) {
+                Some(refs) => {
+                    for change in refs {
+                        let old = change
+                            .old
+                            .map_or("(none)".to_string(), |oid| oid.to_string());
+                        lines.push(format!("      {}: {old} -> {}", change.name, change.new));
+                    }
+                }
+                None => lines.push("      references could not be listed".to_string()),
+            }
+        }
+        if !skipped.is_empty() {
+            lines.push(format!(
+                "Private {} to skip ({}):",
+                title.to_lowercase(),
+                skipped.len()
+            ));
+            for repo in skipped {
+                lines.push(format!("  ! {}", repo.show_full_name()));
+            }
+        }
+    };
+    plan_sync("Repos", to_sync);
+    match forks {
+        Some(forks) => plan_sync("Forks", forks),
+        None => lines.push("Forks: not synced".to_string()),
+    }
+    lines.push(format!("Repos to edit ({}):", to_edit.len()));
+    for repo in to_edit {
+        let fields = changes
+            .get(&repo.path)
+            .map(|changes| changes.metadata.join(", "))
+            .unwrap_or_default();
+        lines.push(format!("  * {}: {fields}", repo.show_full_name()));
+    }
+    match to_delete {
+        Some(to_delete) => {
+            lines.push(format!("Repos to delete ({}):", to_delete.len()));
+            for repo in to_delete {
+                lines.push(format!("  - {}", repo.show_full_name()));
+            }
+        }
+        None => lines.push("Repos to delete: not prompted".to_string()),
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::local::platform::LocalPlatform;
+    use crate::test_utils::local_repo_with_commit;
+
+    #[test]
+    fn metadata() {
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            topics: vec!["Rust".to_string()],
+            has_projects: Some(true),
+            ..Default::default()
+        };
+        let same = Repo {
+            topics: vec!["rust".to_string()],
+            has_projects: None,
+            ..repo.clone()
+        };
+        let fields = [
+            RepoField::Description,
+            RepoField::Homepage,
+            RepoField::Topics,
+        ];
+        assert!(metadata_changes(&repo, &same, &fields).is_empty());
+        let described = Repo {
+            description: "new".to_string(),
+            ..repo.clone()
+        };
+        assert_eq!(
+            metadata_changes(&repo, &described, &fields),
+            vec!["description"]
+        );
+        // a destination without homepage (e.g. Gitlab) never drifts because of it
+        let with_homepage = Repo {
+            homepage: "https://example.com".to_string(),
+            ..repo.clone()
+        };
+        assert_eq!(
+            metadata_changes(&with_homepage, &repo, &fields),
+            vec!["homepage"]
+        );
+        let gitlab_fields = [RepoField::Description, RepoField::Topics];
+        assert!(metadata_changes(&with_homepage, &repo, &gitlab_fields).is_empty());
+    }
+
+    #[tokio::test]
+    async fn diff() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let source = LocalPlatform::new(root.path().join("source"));
+        let destination = LocalPlatform::new(root.path().join("destination"));
+        let repo = |name: &str, description: &str| Repo {
+            name: name.to_string(),
+            path: name.to_string(),
+            description: description.to_string(),
+            ..Default::default()
+        };
+        local_repo_with_commit(&source, &repo("same", ""), &[]).await?;
+        local_repo_with_commit(&source, &repo("edited", "new"), &[]).await?;
+        local_repo_with_commit(&source, &repo("pushed", ""), &[]).await?;
+        local_repo_with_commit(&source, &repo("missing", ""), &[]).await?;
+        for name in ["same", "edited", "pushed"] {
+            destination.create_repo(repo(name, "")).await?;
+            if name != "pushed" {
+                let destination_repo = git2::Repository::open_bare(destination.get_git_url(name))?;
+                destination_repo
+                    .remote_anonymous(&source.get_git_url(name))?
+                    .fetch(&["+refs/heads/main:refs/heads/main"], None, None)?;
+            }
+        }
+        local_repo_with_commit(&destination, &repo("deleted", ""), &[]).await?;
+
+        let diff = diff_repos(
+            Arc::new(Box::new(source.clone())),
+            Arc::new(Box::new(destination.clone())),
+            source.get_all_repos().await?,
+            destination.get_all_repos().await?,
+            RefPolicy::default(),
+        )
+        .await;
+        let names = |repos: &[Repo]| repos.iter().map(|r| r.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&diff.to_sync), vec!["missing", "pushed"]);
+        assert_eq!(names(&diff.to_edit), vec!["edited"]);
+        assert_eq!(names(&diff.to_delete), vec!["deleted"]);
+        assert_eq!(diff.up_to_date, 1);
+        assert_eq!(diff.changes["edited"].metadata, vec!["description"]);
+        let pushed = diff.changes["pushed"].refs.clone().unwrap_or_default();
+        assert_eq!(pushed.len(), 1);
+        assert_eq!(pushed[0].name, "refs/heads/main");
+        assert_eq!(pushed[0].old, None);
+        assert!(!diff.changes.contains_key("missing"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn slugified_destination() -> Result<(), GitMoverError> {
+        use crate::bitbucket_server::platform::BitbucketServerPlatform;
+        let root = tempfile::tempdir()?;
+        let source = LocalPlatform::new(root.path().to_path_buf());
+        let repo = Repo {
+            name: "MyRepo".to_string(),
+            path: "MyRepo".to_string(),
+            ..Default::default()
+        };
+        local_repo_with_commit(&source, &repo, &[]).await?;
+        // nothing listens on this port: the references of the destination are unknown
+        let closed_port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+        let destination = BitbucketServerPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            "http://127.0.0.1",
+            "PROJ".to_string(),
+            Some(closed_port),
+        );
+        let destination_repo = Repo {
+            path: "myrepo".to_string(),
+            ..repo.clone()
+        };
+        let diff = diff_repos(
+            Arc::new(Box::new(source)),
+            Arc::new(Box::new(destination)),
+            vec![repo],
+            vec![destination_repo],
+            RefPolicy::default(),
+        )
+        .await;
+        // the repository is matched by its slug: not deleted, and compared
+        assert!(diff.to_delete.is_empty());
+        assert!(diff.changes.contains_key("MyRepo"));
+        Ok(())
+    }
+
+    #[test]
+    fn plan() {
+        let repo = |name: &str, private: bool| Repo {
+            name: name.to_string(),
+            path: name.to_string(),
+            private,
+            ..Default::default()
+        };
+        let new = git2::Oid::from_bytes(&[1; 20]).unwrap_or(git2::Oid::zero());
+        let changes = HashMap::from([
+            (
+                "pushed".to_string(),
+                RepoChanges {
+                    refs: Some(vec![RefChange {
+                        name: "refs/heads/main".to_string(),
+                        old: None,
+                        new,
+                    }]),
+                    metadata: vec![],
+                },
+            ),
+            (
+                "edited".to_string(),
+                RepoChanges {
+                    refs: Some(vec![]),
+                    metadata: vec!["description".to_string()],
+                },
+            ),
+        ]);
+        let to_sync = [
+            repo("new", false),
+            repo("secret", true),
+            repo("pushed", false),
+        ];
+        let to_edit = [repo("edited", false)];
+        let to_delete = [repo("deleted", false)];
+        let plan = |private| {
+            format_plan(
+                &changes,
+                &to_sync,
+                None,
+                &to_edit,
+                Some(&to_delete),
+                private,
+            )
+        };
+        assert_eq!(
+            plan(Some(PrivatePolicy::Skip)),
+            [
+                "Dry run: nothing will be changed",
+                "Repos to create (1):",
+                "  + new",
+                "Repos to update (1):",
+                "  ~ pushed",
+                "      refs/heads/main: (none) -> 0101010101010101010101010101010101010101",
+                "Private repos to skip (1):",
+                "  ! secret",
+                "Forks: not synced",
+                "Repos to edit (1):",
+                "  * edited: description",
+                "Repos to delete (1):",
+                "  - deleted",
+            ]
+            .join("\n")
+        );
+        assert!(
+            plan(Some(PrivatePolicy::Sync)).contains("Repos to create (2):\n  + new\n  + secret\n")
+        );
+        assert!(plan(None).contains("  + secret (private, asked)\n"));
+    }
+}
@@ -10,6 +10,12 @@ pub struct GitMoverError {
 
     /// Error source
     source: Option<Box<dyn Error + Send + Sync>>,
+
+    /// HTTP status of the failed request, if any
+    status: Option<u16>,
+
+    /// Whether a platform doesn't support the feature
+    not_supported: bool,
 }
 
 impl GitMoverError {
@@ -18,6 +24,16 @@ impl GitMoverError {
         Self {
             message,
             source: None,
+            status: None,
+            not_supported: false,
+        }
+    }
+
+    /// Create an error for a feature not supported by a platform
+    pub(crate) fn new_not_supported(message: String) -> Self {
+        Self {
+            not_supported: true,
+            ..Self::new(message)
         }
     }
 
@@ -29,8 +45,26 @@ impl GitMoverError {
         Self {
             message: message.into(),
             source: Some(Box::new(from)),
+            status: None,
+            not_supported: false,
         }
     }
+
+    /// Attach the HTTP status of the failed request
+    pub(crate) fn with_status(mut self, status: reqwest::StatusCode) -> Self {
+        self.status = Some(status.as_u16());
+        self
+    }
+
+    /// HTTP status of the failed request, if the error comes from an API response
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    /// Whether the error comes from a feature not supported by a platform
+    pub fn is_not_supported(&self) -> bool {
+        self.not_supported
+    }
 }
 
 impl std::error::Error for GitMoverError {}
@@ -0,0 +1,68 @@
+//! Gitea configuration
+use super::platform::GiteaPlatform;
+use crate::{
+    config::GitMoverConfig, config_password_wrap, config_value_wrap, errors::GitMoverError,
+    platform::PlatformType,
+};
+use serde::{Deserialize, Serialize};
+
+/// Gitea configuration
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GiteaConfig {
+    /// Gitea username
+    pub username: Option<String>,
+
+    /// Gitea token
+    pub token: Option<String>,
+
+    /// Gitea instance url
+    pub url: Option<String>,
+
+    /// API prefix (default to `/api/v1`)
+    pub api_prefix: Option<String>,
+
+    /// SSH port (default to 22)
+    pub ssh_port: Option<u16>,
+
+    /// Owner of the repositories, an organization (default to the username)
+    pub owner: Option<String>,
+}
+
+impl GiteaConfig {
+    /// Get the gitea platform
+    pub fn get_plateform(config: &mut GitMoverConfig) -> Result<GiteaPlatform, GitMoverError> {
+        let url = config_value_wrap!(
+            config,
+            gitea,
+            GiteaConfig,
+            url,
+            "your gitea instance url (e.g. https://git.example.com)"
+        );
+        let username =
+            config_value_wrap!(config, gitea, GiteaConfig, username, "your gitea username");
+        let token = config_password_wrap!(
+            config,
+            gitea,
+            GiteaConfig,
+            token,
+            "your gitea token (<instance>/user/settings/applications)"
+        );
+        let (api_prefix, ssh_port, owner) = match &config.config_data.gitea {
+            Some(gitea_config) => (
+                gitea_config.api_prefix.clone(),
+                gitea_config.ssh_port,
+                gitea_config.owner.clone().filter(|owner| !owner.is_empty()),
+            ),
+            None => (None, None, None),
+        };
+        Ok(GiteaPlatform::new(
+            username,
+            token,
+            &url,
+            api_prefix,
+            ssh_port,
+            PlatformType::Gitea,
+        )
+        .with_owner(owner))
+    }
+}
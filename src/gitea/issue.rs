@@ -0,0 +1,116 @@
+//! Gitea issues, comments and labels
+use crate::issue::{normalize_color, Comment, Issue, Label};
+use serde::{Deserialize, Serialize};
+
+/// Gitea user
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GiteaUser {
+    /// Login of the user
+    pub login: String,
+}
+
+/// Gitea label
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GiteaLabel {
+    /// Id of the label
+    #[serde(skip_serializing)]
+    pub id: u64,
+
+    /// Name of the label
+    pub name: String,
+
+    /// Color of the label
+    pub color: String,
+
+    /// Description of the label
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Gitea issue
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GiteaIssue {
+    /// Number of the issue
+    pub number: u64,
+
+    /// Title of the issue
+    pub title: String,
+
+    /// Body of the issue
+    #[serde(default)]
+    pub body: String,
+
+    /// Author of the issue
+    pub user: GiteaUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// State of the issue (`open` or `closed`)
+    pub state: String,
+
+    /// Labels of the issue
+    #[serde(default)]
+    pub labels: Vec<GiteaLabel>,
+}
+
+/// Gitea issue creation
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GiteaIssueCreation {
+    /// Title of the issue
+    pub title: String,
+
+    /// Body of the issue
+    pub body: String,
+
+    /// Ids of the labels of the issue
+    pub labels: Vec<u64>,
+}
+
+/// Gitea issue comment
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GiteaComment {
+    /// Author of the comment
+    pub user: GiteaUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// Body of the comment
+    #[serde(default)]
+    pub body: String,
+}
+
+impl From<GiteaLabel> for Label {
+    fn from(label: GiteaLabel) -> Self {
+        Label {
+            name: label.name,
+            color: normalize_color(&label.color),
+            description: label.description,
+        }
+    }
+}
+
+impl From<GiteaIssue> for Issue {
+    fn from(issue: GiteaIssue) -> Self {
+        Issue {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body,
+            author: issue.user.login,
+            created_at: issue.created_at,
+            closed: issue.state == "closed",
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+        }
+    }
+}
+
+impl From<GiteaComment> for Comment {
+    fn from(comment: GiteaComment) -> Self {
+        Comment {
+            author: comment.user.login,
+            created_at: comment.created_at,
+            body: comment.body,
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Gitea/Forgejo API module.
+pub(crate) mod config;
+pub(crate) mod issue;
+pub(crate) mod platform;
+pub(crate) mod pull_request;
+pub(crate) mod release;
+pub(crate) mod repo;
+
+/// Default Gitea API prefix
+const GITEA_API_PREFIX: &str = "/api/v1";
@@ -0,0 +1,989 @@
+//! Gitea platform implementation
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use urlencoding::encode;
+
+use super::issue::{GiteaComment, GiteaIssue, GiteaIssueCreation, GiteaLabel};
+use super::pull_request::{
+    GiteaPullRequest, GiteaPullRequestCreation, GiteaReview, GiteaReviewComment,
+};
+use super::release::GiteaRelease;
+use super::repo::{GiteaRepo, GiteaRepoEdition, GiteaTopics};
+use super::GITEA_API_PREFIX;
+use crate::{
+    diff::metadata_changes,
+    errors::GitMoverError,
+    issue::{Comment, Issue, Label},
+    lfs::LfsEndpoint,
+    platform::{Platform, PlatformType, RepoField},
+    pull_request::{PullRequest, PullRequestState},
+    release::{Release, ReleaseAsset},
+    utils::{get_host, normalize_url, Repo},
+};
+
+/// Gitea platform, also used for Forgejo instances like Codeberg
+#[derive(Debug, Clone)]
+pub struct GiteaPlatform {
+    /// Gitea username
+    username: String,
+
+    /// Gitea token
+    token: String,
+
+    /// Instance url (with scheme)
+    url: String,
+
+    /// API prefix
+    api_prefix: String,
+
+    /// SSH port
+    ssh_port: Option<u16>,
+
+    /// Organization owning the repositories
+    owner: Option<String>,
+
+    /// Platform type
+    platform_type: PlatformType,
+
+    /// Reqwest client
+    client: reqwest::Client,
+
+    /// Labels of the repositories, fetched once to create their issues
+    labels: Arc<Mutex<HashMap<String, Vec<GiteaLabel>>>>,
+}
+
+impl GiteaPlatform {
+    /// Create a new gitea platform
+    pub fn new(
+        username: String,
+        token: String,
+        url: &str,
+        api_prefix: Option<String>,
+        ssh_port: Option<u16>,
+        platform_type: PlatformType,
+    ) -> Self {
+        let api_prefix = match api_prefix {
+            Some(prefix) if !prefix.is_empty() => {
+                format!("/{}", prefix.trim_matches('/'))
+            }
+            _ => GITEA_API_PREFIX.to_string(),
+        };
+        Self {
+            username,
+            token,
+            url: normalize_url(url),
+            api_prefix,
+            ssh_port,
+            owner: None,
+            platform_type,
+            client: reqwest::Client::new(),
+            labels: Arc::default(),
+        }
+    }
+
+    /// Get the ids of the labels of a repository, fetching them once per repository
+    async fn get_label_ids(
+        &self,
+        labels_url: &str,
+        names: &[String],
+    ) -> Result<Vec<u64>, GitMoverError> {
+        let cached = self.cached_labels().get(labels_url).cloned();
+        let labels = match cached {
+            Some(labels) => labels,
+            None => {
+                let labels: Vec<GiteaLabel> = self.get_pages(labels_url, &[]).await?;
+                self.cached_labels()
+                    .insert(labels_url.to_string(), labels.clone());
+                labels
+            }
+        };
+        Ok(labels
+            .into_iter()
+            .filter(|label| names.contains(&label.name))
+            .map(|label| label.id)
+            .collect())
+    }
+
+    /// Forget the cached labels of a repository after creating one
+    fn forget_labels(&self, labels_url: &str) {
+        self.cached_labels().remove(labels_url);
+    }
+
+    /// Lock the cached labels of the repositories
+    fn cached_labels(&self) -> MutexGuard<'_, HashMap<String, Vec<GiteaLabel>>> {
+        // the cache stays valid even if a thread panicked while holding it
+        self.labels.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Use an organization instead of the user as owner of the repositories
+    pub fn with_owner(mut self, owner: Option<String>) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Get the API url
+    fn api_url(&self) -> String {
+        format!("{}{}", self.url, self.api_prefix)
+    }
+
+    /// Get the API url listing and creating the repositories of the owner
+    fn repos_url(&self) -> String {
+        match &self.owner {
+            Some(owner) => format!("{}/orgs/{}/repos", self.api_url(), encode(owner)),
+            None => format!("{}/user/repos", self.api_url()),
+        }
+    }
+
+    /// Get the API url of a repository of the owner
+    fn repo_url(&self, repo_name: &str) -> String {
+        format!(
+            "{}/repos/{}/{}",
+            self.api_url(),
+            encode(self.get_owner()),
+            encode(repo_name)
+        )
+    }
+
+    /// Build an API request with the Gitea headers
+    fn api_request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .header(AUTHORIZATION, format!("token {}", self.token))
+            .header(ACCEPT, "application/json")
+    }
+
+    /// Send an API request, failing on error statuses
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, GitMoverError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await?;
+            return Err(
+                GitMoverError::new(format!("{text} for {}", self.get_type())).with_status(status),
+            );
+        }
+        Ok(response)
+    }
+
+    /// Send an API request and parse its JSON response
+    async fn send_json<T: DeserializeOwned + Send>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, GitMoverError> {
+        let text = self.send(request).await?.text().await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Get all the pages of an API list
+    async fn get_pages<T: DeserializeOwned + Send>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, GitMoverError> {
+        let mut page: usize = 1;
+        let mut all_items = vec![];
+        loop {
+            let request = self
+                .api_request(Method::GET, url)
+                .query(query)
+                .query(&[("limit", "50"), ("page", &page.to_string())]);
+            let items: Vec<T> = self.send_json(request).await?;
+            if items.is_empty() {
+                break;
+            }
+            all_items.extend(items);
+            page += 1;
+        }
+        Ok(all_items)
+    }
+
+    /// Replace the topics of a repository
+    async fn put_topics(&self, repo: &Repo) -> Result<(), GitMoverError> {
+        let url = format!("{}/topics", self.repo_url(&repo.path));
+        let request = self.api_request(Method::PUT, &url).json(&GiteaTopics {
+            topics: repo.normalized_topics(),
+        });
+        self.send(request).await?;
+        Ok(())
+    }
+}
+
+impl Platform for GiteaPlatform {
+    fn get_remote_url(&self) -> String {
+        get_host(&self.url)
+    }
+
+    fn get_ssh_url(&self) -> String {
+        match self.ssh_port {
+            Some(port) => format!("ssh://git@{}:{}", self.get_remote_url(), port),
+            None => format!("git@{}", self.get_remote_url()),
+        }
+    }
+
+    fn get_git_url(&self, repo_name: &str) -> String {
+        match self.ssh_port {
+            Some(_) => format!(
+                "{}/{}/{}.git",
+                self.get_ssh_url(),
+                self.get_owner(),
+                repo_name
+            ),
+            None => format!(
+                "{}:{}/{}.git",
+                self.get_ssh_url(),
+                self.get_owner(),
+                repo_name
+            ),
+        }
+    }
+
+    fn get_username(&self) -> &str {
+        &self.username
+    }
+
+    fn get_owner(&self) -> &str {
+        match &self.owner {
+            Some(owner) => owner,
+            None => &self.username,
+        }
+    }
+
+    fn get_type(&self) -> PlatformType {
+        self.platform_type.clone()
+    }
+
+    fn get_repo_fields(&self) -> &'static [RepoField] {
+        &[
+            RepoField::Description,
+            RepoField::Private,
+            RepoField::Homepage,
+            RepoField::Archived,
+            RepoField::Topics,
+        ]
+    }
+
+    fn create_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let token = self.token.clone();
+        // the repository is addressed by its path, like the pushes
+        let repo_name = repo.path.to_string();
+        let description = repo.description.to_string();
+        let private = repo.private;
+        let client = self.client.clone();
+        let platform_type = self.get_type();
+        // the creation only sets the name, the description and the visibility
+        let repo = Repo {
+            default_branch: String::new(),
+            archived: false,
+            ..repo
+        };
+        Box::pin(async move {
+            let url = self.repos_url();
+            let json_body = GiteaRepo {
+                name: repo_name.to_string(),
+                description: description.to_string(),
+                private,
+                ..Default::default()
+            };
+            let request = client
+                .post(url)
+                .header(AUTHORIZATION, format!("token {token}"))
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body)
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                let get_repo = match self.get_repo(repo_name.as_str()).await {
+                    Ok(repo) => repo,
+                    Err(e) => {
+                        return Err(GitMoverError::new(format!(
+                            "{text} for {platform_type}: {e}"
+                        )));
+                    }
+                };
+                let changes = metadata_changes(&repo, &get_repo, self.get_repo_fields());
+                if changes.is_empty() {
+                    return Ok(());
+                }
+                return self.edit_repo(repo).await;
+            }
+            // the other settings can only be set by editing the repository,
+            // the features being enabled by default
+            if !repo.homepage.is_empty()
+                || !repo.topics.is_empty()
+                || [repo.has_issues, repo.has_wiki, repo.has_projects].contains(&Some(false))
+            {
+                return self.edit_repo(repo).await;
+            }
+            Ok(())
+        })
+    }
+
+    fn get_repo(
+        &self,
+        repo_name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Repo, GitMoverError>> + Send + '_>> {
+        let token = self.token.clone();
+        let url = self.repo_url(repo_name);
+        let client = self.client.clone();
+        Box::pin(async move {
+            let request = client
+                .get(&url)
+                .header(AUTHORIZATION, format!("token {token}"))
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    self.get_type()
+                )));
+            }
+            let repo: GiteaRepo = response.json().await?;
+            Ok(repo.into())
+        })
+    }
+
+    fn edit_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let repo = repo.clone();
+        let token = self.token.clone();
+        let client = self.client.clone();
+        let url = self.repo_url(&repo.path);
+        Box::pin(async move {
+            let json_body = GiteaRepoEdition::from(&repo);
+            let request = client
+                .patch(url)
+                .header(AUTHORIZATION, format!("token {token}"))
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body)
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    self.get_type()
+                )));
+            }
+            self.put_topics(&repo).await
+        })
+    }
+
+    fn get_all_repos(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Repo>, GitMoverError>> + Send>> {
+        let token = self.token.clone();
+        let client = self.client.clone();
+        let url = self.repos_url();
+        let platform_type = self.get_type();
+        Box::pin(async move {
+            let mut page: usize = 1;
+            let limit = 100;
+            let mut all_repos = Vec::new();
+            loop {
+                let request = client
+                    .get(&url)
+                    .header(AUTHORIZATION, format!("token {token}"))
+                    .header(ACCEPT, "application/json")
+                    .query(&[("page", &page.to_string()), ("limit", &limit.to_string())])
+                    .send();
+
+                let response = request.await?;
+                if !response.status().is_success() {
+                    let text = response.text().await?;
+                    return Err(GitMoverError::new(format!("{text} for {platform_type}")));
+                }
+                let text = response.text().await?;
+                let repos: Vec<GiteaRepo> = serde_json::from_str(&text)?;
+                let mut page_repos: Vec<Repo> = repos.into_iter().map(|r| r.into()).collect();
+                if page_repos.is_empty() {
+                    break;
+                }
+                println!(
+                    "Requested {platform_type} (page {}): {}",
+                    page,
+                    page_repos.len()
+                );
+                all_repos.append(&mut page_repos);
+                page += 1;
+            }
+            Ok(all_repos)
+        })
+    }
+
+    fn delete_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let token = self.token.clone();
+        let name = name.to_string();
+        let client = self.client.clone();
+        Box::pin(async move {
+            let url = format!(
+                "{}/repos/{}/{}",
+                self.api_url(),
+                self.get_owner(),
+                encode(&name)
+            );
+            let request = client
+                .delete(&url)
+                .header(AUTHORIZATION, format!("token {token}"))
+                .header(ACCEPT, "application/json")
+                .send();
+
+            let response = request.await?;
+            if !response.status().is_success() {
+                let text = response.text().await?;
+                return Err(GitMoverError::new(format!(
+                    "{text} for {}",
+                    self.get_type()
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    fn get_labels(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Label>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/labels", self.repo_url(repo_path));
+        Box::pin(async move {
+            let labels: Vec<GiteaLabel> = self.get_pages(&url, &[]).await?;
+            Ok(labels.into_iter().map(|l| l.into()).collect())
+        })
+    }
+
+    fn create_label(
+        &self,
+        repo_path: &str,
+        label: Label,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = format!("{}/labels", self.repo_url(repo_path));
+        Box::pin(async move {
+            let json_body = GiteaLabel {
+                id: 0, // unused
+                name: label.name,
+                color: format!("#{}", label.color),
+                description: label.description,
+            };
+            let request = self
+                .api_request(Method::POST, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body);
+            let _: Value = self.send_json(request).await?;
+            self.forget_labels(&url);
+            Ok(())
+        })
+    }
+
+    fn get_issues(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Issue>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/issues", self.repo_url(repo_path));
+        Box::pin(async move {
+            let issues: Vec<GiteaIssue> = self
+                .get_pages(&url, &[("state", "all"), ("type", "issues")])
+                .await?;
+            Ok(issues.into_iter().map(|issue| issue.into()).collect())
+        })
+    }
+
+    fn create_issue(
+        &self,
+        repo_path: &str,
+        issue: Issue,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<u64, GitMoverError>> + Send + '_>> {
+        let labels_url = format!("{}/labels", self.repo_url(repo_path));
+        let url = format!("{}/issues", self.repo_url(repo_path));
+        Box::pin(async move {
+            // gitea needs the ids of the labels
+            let labels = self.get_label_ids(&labels_url, &issue.labels).await?;
+            let json_body = GiteaIssueCreation {
+                title: issue.title,
+                body: issue.body,
+                labels,
+            };
+            let request = self
+                .api_request(Method::POST, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body);
+            let created: GiteaIssue = self.send_json(request).await?;
+            if issue.closed {
+                let url = format!("{url}/{}", created.number);
+                let request = self
+                    .api_request(Method::PATCH, &url)
+                    .json(&json!({ "state": "closed" }));
+                let _: Value = self.send_json(request).await?;
+            }
+            Ok(created.number)
+        })
+    }
+
+    fn get_issue_comments(
+        &self,
+        repo_path: &str,
+        number: u64,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Comment>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/issues/{number}/comments", self.repo_url(repo_path));
+        Box::pin(async move {
+            // comments of an issue are not paginated
+            let request = self.api_request(Method::GET, &url);
+            let comments: Vec<GiteaComment> = self.send_json(request).await?;
+            Ok(comments.into_iter().map(|c| c.into()).collect())
+        })
+    }
+
+    fn create_issue_comment(
+        &self,
+        repo_path: &str,
+        number: u64,
+        comment: Comment,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = format!("{}/issues/{number}/comments", self.repo_url(repo_path));
+        Box::pin(async move {
+            let request = self
+                .api_request(Method::POST, &url)
+                .json(&json!({ "body": comment.body }));
+            let _: Value = self.send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn get_pull_requests(
+        &self,
+        repo_path: &str,
+    ) -> Pin<
+        Box<dyn std::future::Future<Output = Result<Vec<PullRequest>, GitMoverError>> + Send + '_>,
+    > {
+        let url = format!("{}/pulls", self.repo_url(repo_path));
+        Box::pin(async move {
+            let pull_requests: Vec<GiteaPullRequest> =
+                self.get_pages(&url, &[("state", "all")]).await?;
+            Ok(pull_requests.into_iter().map(|pr| pr.into()).collect())
+        })
+    }
+
+    fn create_pull_request(
+        &self,
+        repo_path: &str,
+        pull_request: PullRequest,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<u64, GitMoverError>> + Send + '_>> {
+        let url = format!("{}/pulls", self.repo_url(repo_path));
+        Box::pin(async move {
+            let json_body = GiteaPullRequestCreation {
+                title: pull_request.title,
+                body: pull_request.body,
+                head: pull_request.source_branch,
+                base: pull_request.target_branch,
+            };
+            let request = self
+                .api_request(Method::POST, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body);
+            let created: GiteaPullRequest = self.send_json(request).await?;
+            if pull_request.state != PullRequestState::Open {
+                let url = format!("{url}/{}", created.number);
+                let request = self
+                    .api_request(Method::PATCH, &url)
+                    .json(&json!({ "state": "closed" }));
+                let _: Value = self.send_json(request).await?;
+            }
+            Ok(created.number)
+        })
+    }
+
+    fn get_pull_request_comments(
+        &self,
+        repo_path: &str,
+        number: u64,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Comment>, GitMoverError>> + Send + '_>>
+    {
+        let issue_comments = self.get_issue_comments(repo_path, number);
+        let reviews_url = format!("{}/pulls/{number}/reviews", self.repo_url(repo_path));
+        Box::pin(async move {
+            let mut comments = issue_comments.await?;
+            let reviews: Vec<GiteaReview> = self.get_pages(&reviews_url, &[]).await?;
+            for review in reviews {
+                if review.comments_count > 0 {
+                    // comments of a review are not paginated
+                    let url = format!("{reviews_url}/{}/comments", review.id);
+                    let request = self.api_request(Method::GET, &url);
+                    let review_comments: Vec<GiteaReviewComment> = self.send_json(request).await?;
+                    comments.extend(review_comments.into_iter().map(|c| c.into()));
+                }
+                if !review.body.is_empty() {
+                    comments.push(review.into());
+                }
+            }
+            comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+            Ok(comments)
+        })
+    }
+
+    fn create_pull_request_comment(
+        &self,
+        repo_path: &str,
+        number: u64,
+        comment: Comment,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        self.create_issue_comment(repo_path, number, comment)
+    }
+
+    fn get_releases(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Release>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/releases", self.repo_url(repo_path));
+        Box::pin(async move {
+            let releases: Vec<GiteaRelease> = self.get_pages(&url, &[]).await?;
+            Ok(releases.into_iter().map(|r| r.into()).collect())
+        })
+    }
+
+    fn create_release(
+        &self,
+        repo_path: &str,
+        release: Release,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<String, GitMoverError>> + Send + '_>> {
+        let url = format!("{}/releases", self.repo_url(repo_path));
+        Box::pin(async move {
+            let json_body: GiteaRelease = release.into();
+            let request = self
+                .api_request(Method::POST, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body);
+            let created: GiteaRelease = self.send_json(request).await?;
+            Ok(Release::from(created).reference)
+        })
+    }
+
+    fn download_release_asset(
+        &self,
+        _repo_path: &str,
+        asset: ReleaseAsset,
+    ) -> Pin<
+        Box<dyn std::future::Future<Output = Result<reqwest::Response, GitMoverError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let request = self
+                .client
+                .get(&asset.download_url)
+                .header(AUTHORIZATION, format!("token {}", self.token));
+            self.send(request).await
+        })
+    }
+
+    fn upload_release_asset(
+        &self,
+        repo_path: &str,
+        release_reference: &str,
+        asset: ReleaseAsset,
+        content: reqwest::Body,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = format!(
+            "{}/releases/{}/assets",
+            self.repo_url(repo_path),
+            encode(release_reference)
+        );
+        Box::pin(async move {
+            let part = match asset.size {
+                0 => Part::stream(content),
+                size => Part::stream_with_length(content, size),
+            }
+            .file_name(asset.name.clone())
+            .mime_str(&asset.content_type)?;
+            let request = self
+                .api_request(Method::POST, &url)
+                .query(&[("name", asset.name.as_str())])
+                .multipart(Form::new().part("attachment", part));
+            let _: Value = self.send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn get_lfs_endpoint(&self, repo_path: &str) -> Result<LfsEndpoint, GitMoverError> {
+        Ok(LfsEndpoint {
+            url: format!(
+                "{}/{}/{}.git/info/lfs",
+                self.url,
+                self.get_owner(),
+                repo_path
+            ),
+            username: self.username.clone(),
+            token: self.token.clone(),
+        })
+    }
+
+    fn set_default_branch(
+        &self,
+        repo_path: &str,
+        branch: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = self.repo_url(repo_path);
+        let json_body = json!({ "default_branch": branch });
+        Box::pin(async move {
+            let request = self
+                .api_request(Method::PATCH, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body);
+            let _: Value = self.send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn has_wiki(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<bool, GitMoverError>> + Send + '_>> {
+        let url = self.repo_url(repo_path);
+        Box::pin(async move {
+            let repo: GiteaRepo = self.send_json(self.api_request(Method::GET, &url)).await?;
+            Ok(repo.has_wiki.unwrap_or(false))
+        })
+    }
+
+    fn enable_wiki(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = self.repo_url(repo_path);
+        Box::pin(async move {
+            let request = self
+                .api_request(Method::PATCH, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json!({ "has_wiki": true }));
+            let _: Value = self.send_json(request).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn organization_repos() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/orgs/org/repos"))
+            .and(header("authorization", "token token"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "repo", "description": "desc", "private": true, "fork": false}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/orgs/org/repos"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/orgs/org/repos"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/repos/org/repo"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GiteaPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            &server.uri(),
+            None,
+            None,
+            PlatformType::Codeberg,
+        )
+        .with_owner(Some("org".to_string()));
+        assert_eq!(platform.get_owner(), "org");
+        assert!(platform.get_git_url("repo").ends_with(":org/repo.git"));
+        let repos = platform.get_all_repos().await?;
+        assert_eq!(repos.len(), 1);
+        assert!(repos[0].private);
+        platform.create_repo(repos[0].clone()).await?;
+        platform.delete_repo("repo").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn edit_repo_path() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(path("/api/v1/repos/my%20org/my-repo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/repos/my%20org/my-repo/topics"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GiteaPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            &server.uri(),
+            None,
+            None,
+            PlatformType::Gitea,
+        )
+        .with_owner(Some("my org".to_string()));
+        let repo = Repo {
+            name: "My Repo".to_string(),
+            path: "my-repo".to_string(),
+            ..Default::default()
+        };
+        platform.edit_repo(repo).await?;
+        let requests = server.received_requests().await.unwrap_or_default();
+        let edition: serde_json::Value = requests
+            .iter()
+            .find(|request| request.method == wiremock::http::Method::PATCH)
+            .map(|request| request.body_json())
+            .transpose()?
+            .unwrap_or_default();
+        assert!(edition.get("name").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn issues() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/labels"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": 5, "name": "bug", "color": "ee0701", "description": ""},
+                {"id": 6, "name": "ui", "color": "#00aabb"}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/labels"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/issues"))
+            .and(body_partial_json(serde_json::json!({"title": "Bug", "labels": [5]})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!(
+                {"number": 9, "title": "Bug", "body": "", "user": {"login": "user"}, "created_at": "2024-02-01T00:00:00Z", "state": "open"}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GiteaPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            &server.uri(),
+            None,
+            None,
+            PlatformType::Gitea,
+        );
+        let labels = platform.get_labels("repo").await?;
+        assert_eq!(labels[1].color, "00aabb");
+        let issue = Issue {
+            title: "Bug".to_string(),
+            labels: vec!["bug".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(platform.create_issue("repo", issue).await?, 9);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pull_request_comments() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/issues/3/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"user": {"login": "alice"}, "created_at": "2024-01-01T00:00:00Z", "body": "Looks good"}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/pulls/3/reviews"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": 7, "user": {"login": "bob"}, "submitted_at": "2024-01-03T00:00:00Z", "body": "Some remarks", "comments_count": 1},
+                {"id": 8, "user": {"login": "carol"}, "submitted_at": "2024-01-04T00:00:00Z", "body": "", "comments_count": 0}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/pulls/3/reviews"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/pulls/3/reviews/7/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"user": {"login": "bob"}, "created_at": "2024-01-02T00:00:00Z", "body": "Typo", "path": "src/lib.rs", "position": 0, "original_position": 4}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/pulls/3/reviews/8/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let platform = GiteaPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            &server.uri(),
+            None,
+            None,
+            PlatformType::Gitea,
+        );
+        let comments = platform.get_pull_request_comments("repo", 3).await?;
+        let bodies: Vec<&str> = comments.iter().map(|c| c.body.as_str()).collect();
+        assert_eq!(
+            bodies,
+            vec![
+                "Looks good",
+                "On `src/lib.rs` line 4:\n\nTypo",
+                "Some remarks"
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+//! Gitea pull requests and reviews
+use super::issue::GiteaUser;
+use crate::issue::Comment;
+use crate::pull_request::{with_file_reference, PullRequest, PullRequestState};
+use serde::{Deserialize, Serialize};
+
+/// Gitea branch of a pull request
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GiteaPullRequestBranch {
+    /// Name of the branch
+    #[serde(rename = "ref")]
+    pub branch: String,
+}
+
+/// Gitea pull request
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GiteaPullRequest {
+    /// Number of the pull request
+    pub number: u64,
+
+    /// Title of the pull request
+    pub title: String,
+
+    /// Body of the pull request
+    #[serde(default)]
+    pub body: String,
+
+    /// Author of the pull request
+    pub user: GiteaUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// State of the pull request (`open` or `closed`)
+    pub state: String,
+
+    /// Whether the pull request is merged
+    #[serde(default)]
+    pub merged: bool,
+
+    /// Branch to merge
+    pub head: GiteaPullRequestBranch,
+
+    /// Branch to merge into
+    pub base: GiteaPullRequestBranch,
+}
+
+/// Gitea review of a pull request
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GiteaReview {
+    /// Id of the review
+    pub id: u64,
+
+    /// Author of the review
+    pub user: GiteaUser,
+
+    /// Submission date
+    #[serde(default)]
+    pub submitted_at: String,
+
+    /// Body of the review
+    #[serde(default)]
+    pub body: String,
+
+    /// Number of comments of the review
+    #[serde(default)]
+    pub comments_count: u64,
+}
+
+/// Gitea comment of a review, on a file
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GiteaReviewComment {
+    /// Author of the comment
+    pub user: GiteaUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// Body of the comment
+    #[serde(default)]
+    pub body: String,
+
+    /// File of the comment
+    pub path: String,
+
+    /// Line of the comment in the new file (0 if outdated)
+    #[serde(default)]
+    pub position: u64,
+
+    /// Line of the comment when it was created
+    #[serde(default)]
+    pub original_position: u64,
+}
+
+/// Gitea pull request creation
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GiteaPullRequestCreation {
+    /// Title of the pull request
+    pub title: String,
+
+    /// Body of the pull request
+    pub body: String,
+
+    /// Branch to merge
+    pub head: String,
+
+    /// Branch to merge into
+    pub base: String,
+}
+
+impl From<GiteaPullRequest> for PullRequest {
+    fn from(pull_request: GiteaPullRequest) -> Self {
+        let state = match (pull_request.merged, pull_request.state.as_str()) {
+            (true, _) => PullRequestState::Merged,
+            (false, "closed") => PullRequestState::Closed,
+            (false, _) => PullRequestState::Open,
+        };
+        PullRequest {
+            number: pull_request.number,
+            title: pull_request.title,
+            body: pull_request.body,
+            author: pull_request.user.login,
+            created_at: pull_request.created_at,
+            source_branch: pull_request.head.branch,
+            target_branch: pull_request.base.branch,
+            state,
+        }
+    }
+}
+
+impl From<GiteaReview> for Comment {
+    fn from(review: GiteaReview) -> Self {
+        Comment {
+            author: review.user.login,
+            created_at: review.submitted_at,
+            body: review.body,
+        }
+    }
+}
+
+impl From<GiteaReviewComment> for Comment {
+    fn from(comment: GiteaReviewComment) -> Self {
+        let line = [comment.position, comment.original_position]
+            .into_iter()
+            .find(|line| *line > 0);
+        Comment {
+            author: comment.user.login,
+            created_at: comment.created_at,
+            body: with_file_reference(&comment.path, line, &comment.body),
+        }
+    }
+}
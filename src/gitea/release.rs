@@ -0,0 +1,83 @@
+//! Gitea releases and release attachments
+use crate::release::{Release, ReleaseAsset, DEFAULT_CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+
+/// Gitea release attachment
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GiteaAttachment {
+    /// File name of the attachment
+    pub name: String,
+
+    /// Size of the attachment in bytes
+    pub size: u64,
+
+    /// Url to download the attachment
+    pub browser_download_url: String,
+}
+
+/// Gitea release
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GiteaRelease {
+    /// Id of the release
+    #[serde(skip_serializing)]
+    pub id: u64,
+
+    /// Tag of the release
+    pub tag_name: String,
+
+    /// Name of the release
+    pub name: String,
+
+    /// Notes of the release
+    #[serde(default)]
+    pub body: String,
+
+    /// Whether the release is a draft
+    pub draft: bool,
+
+    /// Whether the release is a prerelease
+    pub prerelease: bool,
+
+    /// Attachments of the release
+    #[serde(default, skip_serializing)]
+    pub assets: Vec<GiteaAttachment>,
+}
+
+impl From<GiteaAttachment> for ReleaseAsset {
+    fn from(attachment: GiteaAttachment) -> Self {
+        ReleaseAsset {
+            name: attachment.name,
+            size: attachment.size,
+            content_type: DEFAULT_CONTENT_TYPE.to_string(),
+            download_url: attachment.browser_download_url,
+        }
+    }
+}
+
+impl From<GiteaRelease> for Release {
+    fn from(release: GiteaRelease) -> Self {
+        Release {
+            tag_name: release.tag_name,
+            name: release.name,
+            body: release.body,
+            draft: release.draft,
+            prerelease: release.prerelease,
+            assets: release.assets.into_iter().map(|a| a.into()).collect(),
+            reference: release.id.to_string(),
+        }
+    }
+}
+
+impl From<Release> for GiteaRelease {
+    fn from(release: Release) -> Self {
+        GiteaRelease {
+            id: 0, // unused
+            tag_name: release.tag_name,
+            name: release.name,
+            body: release.body,
+            draft: release.draft,
+            prerelease: release.prerelease,
+            assets: vec![], // unused
+        }
+    }
+}
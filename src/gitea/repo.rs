@@ -0,0 +1,124 @@
+//! Gitea repository
+use crate::utils::Repo;
+use serde::{Deserialize, Serialize};
+
+/// Gitea repository
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GiteaRepo {
+    /// Name of the repository
+    pub name: String,
+
+    /// Description of the repository
+    pub description: String,
+
+    /// Whether the repository is private
+    pub private: bool,
+
+    /// Whether the repository is a fork
+    #[serde(skip_serializing)]
+    pub fork: bool,
+
+    /// Whether the wiki is enabled
+    #[serde(default, skip_serializing)]
+    pub has_wiki: Option<bool>,
+
+    /// Whether the issues are enabled
+    #[serde(default, skip_serializing)]
+    pub has_issues: Option<bool>,
+
+    /// Whether the projects are enabled
+    #[serde(default, skip_serializing)]
+    pub has_projects: Option<bool>,
+
+    /// Topics of the repository
+    #[serde(default, skip_serializing)]
+    pub topics: Vec<String>,
+
+    /// Website of the repository
+    #[serde(default, skip_serializing)]
+    pub website: String,
+
+    /// Default branch of the repository
+    #[serde(default, skip_serializing)]
+    pub default_branch: String,
+
+    /// Whether the repository is archived
+    #[serde(default, skip_serializing)]
+    pub archived: bool,
+}
+
+/// Gitea repository edition payload
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GiteaRepoEdition {
+    /// Description of the repository
+    pub description: String,
+
+    /// Website of the repository
+    pub website: String,
+
+    /// Whether the repository is private
+    pub private: bool,
+
+    /// Whether the issues are enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_issues: Option<bool>,
+
+    /// Whether the wiki is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_wiki: Option<bool>,
+
+    /// Whether the projects are enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_projects: Option<bool>,
+
+    /// Default branch of the repository (the branch must exist)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+
+    /// Whether the repository is archived
+    pub archived: bool,
+}
+
+/// Gitea topics of a repository
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GiteaTopics {
+    /// Topics (lowercase, with dashes instead of spaces)
+    pub topics: Vec<String>,
+}
+
+impl From<&Repo> for GiteaRepoEdition {
+    fn from(repo: &Repo) -> Self {
+        GiteaRepoEdition {
+            description: repo.description.clone(),
+            website: repo.homepage.clone(),
+            private: repo.private,
+            has_issues: repo.has_issues,
+            has_wiki: repo.has_wiki,
+            has_projects: repo.has_projects,
+            default_branch: Some(repo.default_branch.clone()).filter(|branch| !branch.is_empty()),
+            archived: repo.archived,
+        }
+    }
+}
+
+impl From<GiteaRepo> for Repo {
+    fn from(repo: GiteaRepo) -> Self {
+        let mut topics = repo.topics;
+        topics.sort();
+        Repo {
+            name: repo.name.clone(),
+            path: repo.name,
+            description: repo.description,
+            private: repo.private,
+            fork: repo.fork,
+            mapped_path: None,
+            topics,
+            homepage: repo.website,
+            default_branch: repo.default_branch,
+            archived: repo.archived,
+            has_issues: repo.has_issues,
+            has_wiki: repo.has_wiki,
+            has_projects: repo.has_projects,
+        }
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     config::GitMoverConfig, config_password_wrap, config_value_wrap, errors::GitMoverError,
+    platform::PlatformType,
 };
 
 /// Github configuration
@@ -14,6 +15,9 @@ pub struct GithubConfig {
 
     /// Github token
     pub token: Option<String>,
+
+    /// Owner of the repositories, an organization (default to the username)
+    pub owner: Option<String>,
 }
 
 impl GithubConfig {
@@ -33,6 +37,68 @@ impl GithubConfig {
             token,
             "your github token (https://github.com/settings/personal-access-tokens)"
         );
-        Ok(GithubPlatform::new(username, token))
+        let owner = match &config.config_data.github {
+            Some(github_config) => github_config
+                .owner
+                .clone()
+                .filter(|owner| !owner.is_empty()),
+            None => None,
+        };
+        Ok(GithubPlatform::new(username, token, None, PlatformType::Github).with_owner(owner))
+    }
+}
+
+/// Github Enterprise Server configuration, separate from the github.com one
+/// to move repositories between both
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GithubEnterpriseConfig {
+    /// Github Enterprise Server url
+    pub url: Option<String>,
+
+    /// Github Enterprise Server username
+    pub username: Option<String>,
+
+    /// Github Enterprise Server token
+    pub token: Option<String>,
+
+    /// Owner of the repositories, an organization (default to the username)
+    pub owner: Option<String>,
+}
+
+impl GithubEnterpriseConfig {
+    /// Get the github platform of the Github Enterprise Server
+    pub fn get_plateform(config: &mut GitMoverConfig) -> Result<GithubPlatform, GitMoverError> {
+        let url = config_value_wrap!(
+            config,
+            github_enterprise,
+            GithubEnterpriseConfig,
+            url,
+            "your github enterprise server url (e.g. https://github.example.com)"
+        );
+        let username = config_value_wrap!(
+            config,
+            github_enterprise,
+            GithubEnterpriseConfig,
+            username,
+            "your github enterprise server username"
+        );
+        let token = config_password_wrap!(
+            config,
+            github_enterprise,
+            GithubEnterpriseConfig,
+            token,
+            "your github enterprise server token (<instance>/settings/tokens)"
+        );
+        let owner = match &config.config_data.github_enterprise {
+            Some(github_config) => github_config
+                .owner
+                .clone()
+                .filter(|owner| !owner.is_empty()),
+            None => None,
+        };
+        Ok(
+            GithubPlatform::new(username, token, Some(url), PlatformType::GithubEnterprise)
+                .with_owner(owner),
+        )
     }
 }
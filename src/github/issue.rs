@@ -0,0 +1,111 @@
+//! Github issues, comments and labels
+use crate::issue::{normalize_color, Comment, Issue, Label};
+use serde::{Deserialize, Serialize};
+
+/// Github user
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GithubUser {
+    /// Login of the user
+    pub login: String,
+}
+
+/// Github label
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GithubLabel {
+    /// Name of the label
+    pub name: String,
+
+    /// Color of the label (without `#`)
+    pub color: String,
+
+    /// Description of the label
+    pub description: Option<String>,
+}
+
+/// Github issue
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GithubIssue {
+    /// Number of the issue
+    pub number: u64,
+
+    /// Title of the issue
+    pub title: String,
+
+    /// Body of the issue
+    pub body: Option<String>,
+
+    /// Author of the issue
+    pub user: GithubUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// State of the issue (`open` or `closed`)
+    pub state: String,
+
+    /// Labels of the issue
+    pub labels: Vec<GithubLabel>,
+
+    /// Set when the issue is a pull request
+    pub pull_request: Option<serde_json::Value>,
+}
+
+/// Github issue creation
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GithubIssueCreation {
+    /// Title of the issue
+    pub title: String,
+
+    /// Body of the issue
+    pub body: String,
+
+    /// Names of the labels of the issue
+    pub labels: Vec<String>,
+}
+
+/// Github issue comment
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GithubComment {
+    /// Author of the comment
+    pub user: GithubUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// Body of the comment
+    pub body: Option<String>,
+}
+
+impl From<GithubLabel> for Label {
+    fn from(label: GithubLabel) -> Self {
+        Label {
+            name: label.name,
+            color: normalize_color(&label.color),
+            description: label.description.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<GithubIssue> for Issue {
+    fn from(issue: GithubIssue) -> Self {
+        Issue {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body.unwrap_or_default(),
+            author: issue.user.login,
+            created_at: issue.created_at,
+            closed: issue.state == "closed",
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+        }
+    }
+}
+
+impl From<GithubComment> for Comment {
+    fn from(comment: GithubComment) -> Self {
+        Comment {
+            author: comment.user.login,
+            created_at: comment.created_at,
+            body: comment.body.unwrap_or_default(),
+        }
+    }
+}
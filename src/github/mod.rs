@@ -1,6 +1,9 @@
 //! GitHub API module.
 pub(crate) mod config;
+pub(crate) mod issue;
 pub(crate) mod platform;
+pub(crate) mod pull_request;
+pub(crate) mod release;
 pub(crate) mod repo;
 
 /// GitHub URL
@@ -1,19 +1,30 @@
 //! Github Platform
-use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
 use std::pin::Pin;
 use urlencoding::encode;
 
+use super::issue::{GithubComment, GithubIssue, GithubIssueCreation, GithubLabel};
+use super::pull_request::{GithubPullRequest, GithubPullRequestCreation, GithubReviewComment};
+use super::release::{GithubRelease, GithubReleaseCreation};
 use super::{GITHUB_API_HEADER, GITHUB_API_URL, GITHUB_API_VERSION, GITHUB_URL};
 
 use crate::{
+    diff::metadata_changes,
     errors::GitMoverError,
-    github::repo::RepoGithub,
-    platform::{Platform, PlatformType},
-    utils::Repo,
+    github::repo::{GithubRepoEdition, GithubTopics, RepoGithub},
+    issue::{Comment, Issue, Label},
+    lfs::LfsEndpoint,
+    platform::{Platform, PlatformType, RepoField},
+    pull_request::{PullRequest, PullRequestState},
+    release::{Release, ReleaseAsset},
+    utils::{get_host, normalize_url, Repo},
 };
 
 /// Github Platform
-#[derive(Default, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct GithubPlatform {
     /// Github username
     username: String,
@@ -23,30 +34,171 @@ pub struct GithubPlatform {
 
     /// Reqwest client
     client: reqwest::Client,
+
+    /// Instance url (with scheme)
+    url: String,
+
+    /// API url
+    api_url: String,
+
+    /// Owner of the repositories (the username or an organization)
+    owner: String,
+
+    /// Platform type (Github or Github Enterprise Server)
+    platform_type: PlatformType,
 }
 
 impl GithubPlatform {
-    /// Create a new GithubPlatform
-    pub(crate) fn new(username: String, token: String) -> Self {
+    /// Create a new GithubPlatform, using `custom_url` as the Github Enterprise Server url
+    pub(crate) fn new(
+        username: String,
+        token: String,
+        custom_url: Option<String>,
+        platform_type: PlatformType,
+    ) -> Self {
+        let (url, api_url) = match custom_url {
+            Some(cust_url) => {
+                let url = normalize_url(&cust_url);
+                let api_url = format!("{url}/api/v3");
+                (url, api_url)
+            }
+            None => (normalize_url(GITHUB_URL), normalize_url(GITHUB_API_URL)),
+        };
         Self {
+            owner: username.clone(),
             username,
             token,
             client: reqwest::Client::new(),
+            url,
+            api_url,
+            platform_type,
+        }
+    }
+
+    /// Use an organization instead of the user as owner of the repositories
+    pub(crate) fn with_owner(mut self, owner: Option<String>) -> Self {
+        if let Some(owner) = owner {
+            self.owner = owner;
+        }
+        self
+    }
+
+    /// Whether the repositories are owned by an organization
+    fn is_organization(&self) -> bool {
+        !self.owner.eq_ignore_ascii_case(&self.username)
+    }
+
+    /// Get the API url listing and creating the repositories of the owner
+    fn repos_url(&self) -> String {
+        if self.is_organization() {
+            format!("{}/orgs/{}/repos", self.api_url, encode(&self.owner))
+        } else {
+            format!("{}/user/repos", self.api_url)
+        }
+    }
+
+    /// Get the API url of a repository of the owner
+    fn repo_url(&self, repo_name: &str) -> String {
+        format!(
+            "{}/repos/{}/{}",
+            self.api_url,
+            encode(&self.owner),
+            encode(repo_name)
+        )
+    }
+
+    /// Build an API request with the Github headers
+    fn api_request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "reqwest")
+            .header(GITHUB_API_HEADER, GITHUB_API_VERSION)
+    }
+
+    /// Send an API request, failing on error statuses
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, GitMoverError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await?;
+            return Err(
+                GitMoverError::new(format!("{text} for {}", self.platform_type))
+                    .with_status(status),
+            );
         }
+        Ok(response)
+    }
+
+    /// Send an API request and parse its JSON response
+    async fn send_json<T: DeserializeOwned + Send>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, GitMoverError> {
+        let text = self.send(request).await?.text().await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Get all the pages of an API list
+    async fn get_pages<T: DeserializeOwned + Send>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, GitMoverError> {
+        let mut page: usize = 1;
+        let mut all_items = vec![];
+        loop {
+            let request = self
+                .api_request(Method::GET, url)
+                .query(query)
+                .query(&[("per_page", "100"), ("page", &page.to_string())]);
+            let items: Vec<T> = self.send_json(request).await?;
+            if items.is_empty() {
+                break;
+            }
+            all_items.extend(items);
+            page += 1;
+        }
+        Ok(all_items)
+    }
+
+    /// Replace the topics of a repository
+    async fn put_topics(&self, repo: &Repo) -> Result<(), GitMoverError> {
+        let url = format!("{}/topics", self.repo_url(&repo.path));
+        let request = self
+            .api_request(Method::PUT, &url)
+            .json(&GithubTopics::from(repo));
+        let _: Value = self.send_json(request).await?;
+        Ok(())
     }
 }
 
 impl Platform for GithubPlatform {
     fn get_remote_url(&self) -> String {
-        GITHUB_URL.to_string()
+        get_host(&self.url)
     }
 
     fn get_username(&self) -> &str {
         &self.username
     }
 
+    fn get_owner(&self) -> &str {
+        &self.owner
+    }
+
     fn get_type(&self) -> PlatformType {
-        PlatformType::Github
+        self.platform_type.clone()
+    }
+
+    fn get_repo_fields(&self) -> &'static [RepoField] {
+        &[
+            RepoField::Description,
+            RepoField::Private,
+            RepoField::Homepage,
+            RepoField::Archived,
+            RepoField::Topics,
+        ]
     }
 
     fn create_repo(
@@ -57,31 +209,33 @@ impl Platform for GithubPlatform {
         let repo = repo.clone();
         let client = self.client.clone();
         Box::pin(async move {
-            let url = format!("https://{GITHUB_API_URL}/user/repos");
+            let url = self.repos_url();
             let request = client
                 .post(&url)
                 .header(AUTHORIZATION, format!("Bearer {token}"))
                 .header(ACCEPT, "application/vnd.github+json")
                 .header(USER_AGENT, "reqwest")
                 .header(GITHUB_API_HEADER, GITHUB_API_VERSION)
-                .json(&repo)
+                .json(&GithubRepoEdition::creation(&repo))
                 .send();
 
             let response = request.await?;
             if !response.status().is_success() {
                 let text = response.text().await?;
-                let get_repo = match self.get_repo(repo.name.as_str()).await {
+                let get_repo = match self.get_repo(repo.path.as_str()).await {
                     Ok(repo) => repo,
                     Err(e) => {
                         return Err(GitMoverError::new(format!(
                             "{text} for {}: {e}",
-                            PlatformType::Github
+                            self.platform_type
                         )));
                     }
                 };
-                if get_repo != repo {
+                if !metadata_changes(&repo, &get_repo, self.get_repo_fields()).is_empty() {
                     return self.edit_repo(repo).await;
                 }
+            } else if !repo.topics.is_empty() {
+                self.put_topics(&repo).await?;
             }
             Ok(())
         })
@@ -93,30 +247,25 @@ impl Platform for GithubPlatform {
     ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
         let token = self.token.clone();
         let client = self.client.clone();
+        let url = self.repo_url(&repo.path);
         Box::pin(async move {
-            let url = format!(
-                "https://{}/repos/{}/{}",
-                GITHUB_API_URL,
-                self.username,
-                encode(&repo.name)
-            );
             let request = client
                 .patch(&url)
                 .header(AUTHORIZATION, format!("Bearer {token}"))
                 .header(ACCEPT, "application/vnd.github+json")
                 .header(USER_AGENT, "reqwest")
                 .header(GITHUB_API_HEADER, GITHUB_API_VERSION)
-                .json(&repo)
+                .json(&GithubRepoEdition::edition(&repo))
                 .send();
             let response = request.await?;
             if !response.status().is_success() {
                 let text = response.text().await?;
                 return Err(GitMoverError::new(format!(
                     "{text} for {}",
-                    PlatformType::Github
+                    self.platform_type
                 )));
             }
-            Ok(())
+            self.put_topics(&repo).await
         })
     }
 
@@ -125,16 +274,10 @@ impl Platform for GithubPlatform {
         repo_name: &str,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<Repo, GitMoverError>> + Send>> {
         let token = self.token.clone();
-        let username = self.username.clone();
-        let repo_name = repo_name.to_string();
+        let url = self.repo_url(repo_name);
         let client = self.client.clone();
+        let platform_type = self.platform_type.clone();
         Box::pin(async move {
-            let url = format!(
-                "https://{}/repos/{}/{}",
-                GITHUB_API_URL,
-                username,
-                encode(&repo_name)
-            );
             let request = client
                 .get(&url)
                 .header(AUTHORIZATION, format!("Bearer {token}"))
@@ -145,10 +288,7 @@ impl Platform for GithubPlatform {
             let response = request.await?;
             if !response.status().is_success() {
                 let text = response.text().await?;
-                return Err(GitMoverError::new(format!(
-                    "{text} for {}",
-                    PlatformType::Github
-                )));
+                return Err(GitMoverError::new(format!("{text} for {}", platform_type)));
             }
             let text = response.text().await?;
             let repo: RepoGithub = serde_json::from_str(&text)?;
@@ -161,8 +301,15 @@ impl Platform for GithubPlatform {
     ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Repo>, GitMoverError>> + Send>> {
         let token = self.token.clone();
         let client = self.client.clone();
+        let url = self.repos_url();
+        let platform_type = self.platform_type.clone();
+        let repos_type = if self.is_organization() {
+            "all"
+        } else {
+            "owner"
+        };
         Box::pin(async move {
-            let url = &format!("https://{GITHUB_API_URL}/user/repos");
+            let url = &url;
             let mut need_request = true;
             let mut page: usize = 1;
             let mut all_repos = vec![];
@@ -170,7 +317,7 @@ impl Platform for GithubPlatform {
                 let request = client
                     .get(url)
                     .query(&[
-                        ("type", "owner"),
+                        ("type", repos_type),
                         ("per_page", "100"),
                         ("page", &page.to_string()),
                     ])
@@ -182,10 +329,7 @@ impl Platform for GithubPlatform {
                 let response = request.await?;
                 if !response.status().is_success() {
                     let text = response.text().await?;
-                    return Err(GitMoverError::new(format!(
-                        "{text} for {}",
-                        PlatformType::Github
-                    )));
+                    return Err(GitMoverError::new(format!("{text} for {}", platform_type)));
                 }
                 let text = response.text().await?;
                 let repos: Vec<RepoGithub> = serde_json::from_str(&text)?;
@@ -193,7 +337,7 @@ impl Platform for GithubPlatform {
                 if repos.is_empty() {
                     need_request = false;
                 }
-                println!("Requested github (page {}): {}", page, repos.len());
+                println!("Requested {platform_type} (page {}): {}", page, repos.len());
                 all_repos.extend(repos);
                 page += 1;
             }
@@ -206,15 +350,9 @@ impl Platform for GithubPlatform {
         repo_name: &str,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
         let token = self.token.clone();
-        let name = repo_name.to_string();
+        let url = self.repo_url(repo_name);
         let client = self.client.clone();
         Box::pin(async move {
-            let url = format!(
-                "https://{}/repos/{}/{}",
-                GITHUB_API_URL,
-                self.username,
-                encode(&name)
-            );
             let request = client
                 .delete(&url)
                 .header(AUTHORIZATION, format!("Bearer {token}"))
@@ -227,10 +365,686 @@ impl Platform for GithubPlatform {
                 let text = response.text().await?;
                 return Err(GitMoverError::new(format!(
                     "{text} for {}",
-                    PlatformType::Github
+                    self.platform_type
                 )));
             }
             Ok(())
         })
     }
+
+    fn get_labels(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Label>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/labels", self.repo_url(repo_path));
+        Box::pin(async move {
+            let labels: Vec<GithubLabel> = self.get_pages(&url, &[]).await?;
+            Ok(labels.into_iter().map(|l| l.into()).collect())
+        })
+    }
+
+    fn create_label(
+        &self,
+        repo_path: &str,
+        label: Label,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = format!("{}/labels", self.repo_url(repo_path));
+        Box::pin(async move {
+            let json_body = GithubLabel {
+                name: label.name,
+                color: label.color,
+                description: Some(label.description),
+            };
+            let request = self.api_request(Method::POST, &url).json(&json_body);
+            let _: Value = self.send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn get_issues(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Issue>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/issues", self.repo_url(repo_path));
+        Box::pin(async move {
+            let issues: Vec<GithubIssue> = self
+                .get_pages(&url, &[("state", "all"), ("direction", "asc")])
+                .await?;
+            Ok(issues
+                .into_iter()
+                .filter(|issue| issue.pull_request.is_none())
+                .map(|issue| issue.into())
+                .collect())
+        })
+    }
+
+    fn create_issue(
+        &self,
+        repo_path: &str,
+        issue: Issue,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<u64, GitMoverError>> + Send + '_>> {
+        let url = format!("{}/issues", self.repo_url(repo_path));
+        Box::pin(async move {
+            let json_body = GithubIssueCreation {
+                title: issue.title,
+                body: issue.body,
+                labels: issue.labels,
+            };
+            let request = self.api_request(Method::POST, &url).json(&json_body);
+            let created: GithubIssue = self.send_json(request).await?;
+            if issue.closed {
+                let url = format!("{url}/{}", created.number);
+                let request = self
+                    .api_request(Method::PATCH, &url)
+                    .json(&json!({ "state": "closed" }));
+                let _: Value = self.send_json(request).await?;
+            }
+            Ok(created.number)
+        })
+    }
+
+    fn get_issue_comments(
+        &self,
+        repo_path: &str,
+        number: u64,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Comment>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/issues/{number}/comments", self.repo_url(repo_path));
+        Box::pin(async move {
+            let comments: Vec<GithubComment> = self.get_pages(&url, &[]).await?;
+            Ok(comments.into_iter().map(|c| c.into()).collect())
+        })
+    }
+
+    fn create_issue_comment(
+        &self,
+        repo_path: &str,
+        number: u64,
+        comment: Comment,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = format!("{}/issues/{number}/comments", self.repo_url(repo_path));
+        Box::pin(async move {
+            let request = self
+                .api_request(Method::POST, &url)
+                .json(&json!({ "body": comment.body }));
+            let _: Value = self.send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn get_pull_requests(
+        &self,
+        repo_path: &str,
+    ) -> Pin<
+        Box<dyn std::future::Future<Output = Result<Vec<PullRequest>, GitMoverError>> + Send + '_>,
+    > {
+        let url = format!("{}/pulls", self.repo_url(repo_path));
+        Box::pin(async move {
+            let pull_requests: Vec<GithubPullRequest> = self
+                .get_pages(&url, &[("state", "all"), ("direction", "asc")])
+                .await?;
+            Ok(pull_requests.into_iter().map(|pr| pr.into()).collect())
+        })
+    }
+
+    fn create_pull_request(
+        &self,
+        repo_path: &str,
+        pull_request: PullRequest,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<u64, GitMoverError>> + Send + '_>> {
+        let url = format!("{}/pulls", self.repo_url(repo_path));
+        Box::pin(async move {
+            let json_body = GithubPullRequestCreation {
+                title: pull_request.title,
+                body: pull_request.body,
+                head: pull_request.source_branch,
+                base: pull_request.target_branch,
+            };
+            let request = self.api_request(Method::POST, &url).json(&json_body);
+            let created: GithubPullRequest = self.send_json(request).await?;
+            if pull_request.state != PullRequestState::Open {
+                let url = format!("{url}/{}", created.number);
+                let request = self
+                    .api_request(Method::PATCH, &url)
+                    .json(&json!({ "state": "closed" }));
+                let _: Value = self.send_json(request).await?;
+            }
+            Ok(created.number)
+        })
+    }
+
+    fn get_pull_request_comments(
+        &self,
+        repo_path: &str,
+        number: u64,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Comment>, GitMoverError>> + Send + '_>>
+    {
+        let issue_comments = self.get_issue_comments(repo_path, number);
+        let review_url = format!("{}/pulls/{number}/comments", self.repo_url(repo_path));
+        Box::pin(async move {
+            let mut comments = issue_comments.await?;
+            let review_comments: Vec<GithubReviewComment> =
+                self.get_pages(&review_url, &[]).await?;
+            comments.extend(review_comments.into_iter().map(|c| c.into()));
+            comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+            Ok(comments)
+        })
+    }
+
+    fn create_pull_request_comment(
+        &self,
+        repo_path: &str,
+        number: u64,
+        comment: Comment,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        // the comments of the conversation of a pull request are issue comments
+        self.create_issue_comment(repo_path, number, comment)
+    }
+
+    fn get_releases(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Release>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/releases", self.repo_url(repo_path));
+        Box::pin(async move {
+            let releases: Vec<GithubRelease> = self.get_pages(&url, &[]).await?;
+            Ok(releases.into_iter().map(|r| r.into()).collect())
+        })
+    }
+
+    fn create_release(
+        &self,
+        repo_path: &str,
+        release: Release,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<String, GitMoverError>> + Send + '_>> {
+        let url = format!("{}/releases", self.repo_url(repo_path));
+        Box::pin(async move {
+            let json_body: GithubReleaseCreation = release.into();
+            let request = self.api_request(Method::POST, &url).json(&json_body);
+            let created: GithubRelease = self.send_json(request).await?;
+            Ok(Release::from(created).reference)
+        })
+    }
+
+    fn download_release_asset(
+        &self,
+        _repo_path: &str,
+        asset: ReleaseAsset,
+    ) -> Pin<
+        Box<dyn std::future::Future<Output = Result<reqwest::Response, GitMoverError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let request = self
+                .client
+                .get(&asset.download_url)
+                .header(AUTHORIZATION, format!("Bearer {}", self.token))
+                .header(ACCEPT, "application/octet-stream")
+                .header(USER_AGENT, "reqwest")
+                .header(GITHUB_API_HEADER, GITHUB_API_VERSION);
+            self.send(request).await
+        })
+    }
+
+    fn upload_release_asset(
+        &self,
+        _repo_path: &str,
+        release_reference: &str,
+        asset: ReleaseAsset,
+        content: reqwest::Body,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = release_reference.to_string();
+        Box::pin(async move {
+            let mut request = self
+                .api_request(Method::POST, &url)
+                .query(&[("name", asset.name.as_str())])
+                .header(CONTENT_TYPE, asset.content_type)
+                .body(content);
+            if asset.size > 0 {
+                // uploads need a length, the stream doesn't have one
+                request = request.header(CONTENT_LENGTH, asset.size);
+            }
+            self.send(request).await?;
+            Ok(())
+        })
+    }
+
+    fn get_lfs_endpoint(&self, repo_path: &str) -> Result<LfsEndpoint, GitMoverError> {
+        Ok(LfsEndpoint {
+            url: format!(
+                "{}/{}/{}.git/info/lfs",
+                self.url,
+                self.get_owner(),
+                repo_path
+            ),
+            username: self.username.clone(),
+            token: self.token.clone(),
+        })
+    }
+
+    fn set_default_branch(
+        &self,
+        repo_path: &str,
+        branch: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = self.repo_url(repo_path);
+        let json_body = json!({ "default_branch": branch });
+        Box::pin(async move {
+            let request = self.api_request(Method::PATCH, &url).json(&json_body);
+            let _: Value = self.send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn has_wiki(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<bool, GitMoverError>> + Send + '_>> {
+        let url = self.repo_url(repo_path);
+        Box::pin(async move {
+            let repo: RepoGithub = self.send_json(self.api_request(Method::GET, &url)).await?;
+            Ok(repo.has_wiki)
+        })
+    }
+
+    fn enable_wiki(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = self.repo_url(repo_path);
+        Box::pin(async move {
+            let request = self
+                .api_request(Method::PATCH, &url)
+                .json(&json!({ "has_wiki": true }));
+            let _: Value = self.send_json(request).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn enterprise_url() {
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some("ghes.example.com".to_string()),
+            PlatformType::GithubEnterprise,
+        );
+        assert_eq!(platform.get_remote_url(), "ghes.example.com");
+        assert_eq!(platform.api_url, "https://ghes.example.com/api/v3");
+        assert_eq!(platform.get_type(), PlatformType::GithubEnterprise);
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            None,
+            PlatformType::Github,
+        );
+        assert_eq!(platform.get_remote_url(), GITHUB_URL);
+        assert_eq!(platform.api_url, "https://api.github.com");
+        assert_eq!(platform.get_type(), PlatformType::Github);
+    }
+
+    #[tokio::test]
+    async fn enterprise_get_all_repos() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/user/repos"))
+            .and(header(AUTHORIZATION, "Bearer token"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": 1, "name": "repo", "description": null, "private": false, "html_url": "", "fork": false}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/user/repos"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let repos = platform.get_all_repos().await?;
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].name, "repo");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn organization_repos() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/orgs/org/repos"))
+            .and(query_param("type", "all"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": 1, "name": "tool", "description": "org tool", "private": false, "html_url": "", "fork": false}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/orgs/org/repos"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/orgs/org/repos"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        )
+        .with_owner(Some("org".to_string()));
+        let repos = platform.get_all_repos().await?;
+        assert_eq!(repos.len(), 1);
+        platform.create_repo(repos[0].clone()).await?;
+        assert_eq!(
+            platform.get_git_url("tool"),
+            format!("git@{}:org/tool.git", platform.get_remote_url())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn edit_repo_metadata() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(path("/api/v3/repos/user/repo"))
+            .and(body_partial_json(serde_json::json!({
+                "homepage": "https://example.com",
+                "has_issues": false,
+                "default_branch": "main",
+                "archived": true
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/repos/user/repo/topics"))
+            .and(body_partial_json(
+                serde_json::json!({"names": ["git", "rust-lang"]}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            topics: vec!["git".to_string(), "Rust Lang".to_string()],
+            homepage: "https://example.com".to_string(),
+            default_branch: "main".to_string(),
+            archived: true,
+            has_issues: Some(false),
+            ..Default::default()
+        };
+        platform.edit_repo(repo).await?;
+        Ok(())
+    }
+
+    #[test]
+    fn owner_case() {
+        // Github logins are case insensitive
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            None,
+            PlatformType::Github,
+        )
+        .with_owner(Some("User".to_string()));
+        assert!(platform.repos_url().ends_with("/user/repos"));
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            None,
+            PlatformType::Github,
+        )
+        .with_owner(Some("org".to_string()));
+        assert!(platform.repos_url().ends_with("/orgs/org/repos"));
+    }
+
+    #[tokio::test]
+    async fn create_repo_topics() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/user/repos"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // the topics are set on the path of the repository, not its display name
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/repos/user/my-repo/topics"))
+            .and(body_partial_json(serde_json::json!({"names": ["git"]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let repo = Repo {
+            name: "My Repo".to_string(),
+            path: "my-repo".to_string(),
+            topics: vec!["git".to_string()],
+            ..Default::default()
+        };
+        platform.create_repo(repo).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_existing_repo() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/user/repos"))
+            .respond_with(ResponseTemplate::new(422))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/repos/user/repo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!(
+                {"id": 1, "name": "repo", "description": "desc", "private": false, "html_url": "https://github.com/user/repo", "fork": false, "has_issues": true, "topics": ["git"]}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // the fields unknown to the comparison don't trigger an edition
+        Mock::given(method("PATCH"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            description: "desc".to_string(),
+            topics: vec!["Git".to_string()],
+            has_issues: Some(true),
+            mapped_path: Some("repo".to_string()),
+            ..Default::default()
+        };
+        platform.create_repo(repo).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn edit_repo_path() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(path("/api/v3/repos/user/my-repo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/repos/user/my-repo/topics"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let repo = Repo {
+            name: "My Repo".to_string(),
+            path: "my-repo".to_string(),
+            ..Default::default()
+        };
+        platform.edit_repo(repo).await?;
+        // the edition never renames the repository
+        let requests = server.received_requests().await.unwrap_or_default();
+        let edition: serde_json::Value = requests
+            .iter()
+            .find(|request| request.method == wiremock::http::Method::PATCH)
+            .map(|request| request.body_json())
+            .transpose()?
+            .unwrap_or_default();
+        assert!(edition.get("name").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn issues() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/repos/user/repo/issues"))
+            .and(query_param("state", "all"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"number": 1, "title": "Bug", "body": "It fails", "user": {"login": "alice"}, "created_at": "2024-01-01T00:00:00Z", "state": "closed", "labels": [{"name": "bug", "color": "d73a4a", "description": null}]},
+                {"number": 2, "title": "Fix", "body": null, "user": {"login": "bob"}, "created_at": "2024-01-02T00:00:00Z", "state": "open", "labels": [], "pull_request": {}}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/repos/user/repo/issues"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/repos/user/repo/issues"))
+            .and(body_partial_json(serde_json::json!({"title": "Bug", "labels": ["bug"]})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!(
+                {"number": 7, "title": "Bug", "body": "It fails", "user": {"login": "user"}, "created_at": "2024-02-01T00:00:00Z", "state": "open", "labels": []}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/api/v3/repos/user/repo/issues/7"))
+            .and(body_partial_json(serde_json::json!({"state": "closed"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let issues = platform.get_issues("repo").await?;
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].closed);
+        assert_eq!(issues[0].author, "alice");
+        assert_eq!(issues[0].labels, vec!["bug"]);
+        let number = platform.create_issue("repo", issues[0].clone()).await?;
+        assert_eq!(number, 7);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pull_requests() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        for (list_path, items) in [
+            (
+                "/api/v3/repos/user/repo/pulls",
+                serde_json::json!([
+                    {"number": 3, "title": "Feature", "body": null, "user": {"login": "alice"}, "created_at": "2024-01-01", "state": "closed", "merged_at": "2024-01-05", "head": {"ref": "feature"}, "base": {"ref": "main"}}
+                ]),
+            ),
+            (
+                "/api/v3/repos/user/repo/issues/3/comments",
+                serde_json::json!([{"user": {"login": "bob"}, "created_at": "2024-01-03", "body": "LGTM"}]),
+            ),
+            (
+                "/api/v3/repos/user/repo/pulls/3/comments",
+                serde_json::json!([{"user": {"login": "carol"}, "created_at": "2024-01-02", "body": "Nit", "path": "README.md", "line": null}]),
+            ),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(list_path))
+                .and(query_param("page", "1"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(items))
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path(list_path))
+                .and(query_param("page", "2"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let pull_requests = platform.get_pull_requests("repo").await?;
+        assert_eq!(pull_requests[0].state, PullRequestState::Merged);
+        assert_eq!(pull_requests[0].source_branch, "feature");
+        let comments = platform.get_pull_request_comments("repo", 3).await?;
+        assert_eq!(comments[0].body, "On `README.md`:\n\nNit");
+        assert_eq!(comments[1].body, "LGTM");
+        Ok(())
+    }
 }
@@ -0,0 +1,109 @@
+//! Github pull requests and review comments
+use super::issue::GithubUser;
+use crate::issue::Comment;
+use crate::pull_request::{with_file_reference, PullRequest, PullRequestState};
+use serde::{Deserialize, Serialize};
+
+/// Github branch of a pull request
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GithubPullRequestBranch {
+    /// Name of the branch
+    #[serde(rename = "ref")]
+    pub branch: String,
+}
+
+/// Github pull request
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GithubPullRequest {
+    /// Number of the pull request
+    pub number: u64,
+
+    /// Title of the pull request
+    pub title: String,
+
+    /// Body of the pull request
+    pub body: Option<String>,
+
+    /// Author of the pull request
+    pub user: GithubUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// State of the pull request (`open` or `closed`)
+    pub state: String,
+
+    /// Merge date, set when the pull request is merged
+    pub merged_at: Option<String>,
+
+    /// Branch to merge
+    pub head: GithubPullRequestBranch,
+
+    /// Branch to merge into
+    pub base: GithubPullRequestBranch,
+}
+
+/// Github pull request creation
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GithubPullRequestCreation {
+    /// Title of the pull request
+    pub title: String,
+
+    /// Body of the pull request
+    pub body: String,
+
+    /// Branch to merge
+    pub head: String,
+
+    /// Branch to merge into
+    pub base: String,
+}
+
+/// Github review comment, on a line of the diff
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GithubReviewComment {
+    /// Author of the comment
+    pub user: GithubUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// Body of the comment
+    pub body: String,
+
+    /// File of the comment
+    pub path: String,
+
+    /// Line of the comment
+    pub line: Option<u64>,
+}
+
+impl From<GithubPullRequest> for PullRequest {
+    fn from(pull_request: GithubPullRequest) -> Self {
+        let state = match (pull_request.merged_at, pull_request.state.as_str()) {
+            (Some(_), _) => PullRequestState::Merged,
+            (None, "closed") => PullRequestState::Closed,
+            (None, _) => PullRequestState::Open,
+        };
+        PullRequest {
+            number: pull_request.number,
+            title: pull_request.title,
+            body: pull_request.body.unwrap_or_default(),
+            author: pull_request.user.login,
+            created_at: pull_request.created_at,
+            source_branch: pull_request.head.branch,
+            target_branch: pull_request.base.branch,
+            state,
+        }
+    }
+}
+
+impl From<GithubReviewComment> for Comment {
+    fn from(comment: GithubReviewComment) -> Self {
+        Comment {
+            author: comment.user.login,
+            created_at: comment.created_at,
+            body: with_file_reference(&comment.path, comment.line, &comment.body),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Github releases and release assets
+use crate::release::{Release, ReleaseAsset};
+use serde::{Deserialize, Serialize};
+
+/// Github release asset
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GithubReleaseAsset {
+    /// File name of the asset
+    pub name: String,
+
+    /// Size of the asset in bytes
+    pub size: u64,
+
+    /// Content type of the asset
+    pub content_type: String,
+
+    /// API url of the asset, downloading it with `Accept: application/octet-stream`
+    pub url: String,
+}
+
+/// Github release
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GithubRelease {
+    /// Tag of the release
+    pub tag_name: String,
+
+    /// Name of the release
+    pub name: Option<String>,
+
+    /// Notes of the release
+    pub body: Option<String>,
+
+    /// Whether the release is a draft
+    pub draft: bool,
+
+    /// Whether the release is a prerelease
+    pub prerelease: bool,
+
+    /// Url template to upload the assets (`.../assets{?name,label}`)
+    pub upload_url: String,
+
+    /// Assets of the release
+    #[serde(default)]
+    pub assets: Vec<GithubReleaseAsset>,
+}
+
+/// Github release creation
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GithubReleaseCreation {
+    /// Tag of the release
+    pub tag_name: String,
+
+    /// Name of the release
+    pub name: String,
+
+    /// Notes of the release
+    pub body: String,
+
+    /// Whether the release is a draft
+    pub draft: bool,
+
+    /// Whether the release is a prerelease
+    pub prerelease: bool,
+}
+
+impl From<GithubReleaseAsset> for ReleaseAsset {
+    fn from(asset: GithubReleaseAsset) -> Self {
+        ReleaseAsset {
+            name: asset.name,
+            size: asset.size,
+            content_type: asset.content_type,
+            download_url: asset.url,
+        }
+    }
+}
+
+impl From<GithubRelease> for Release {
+    fn from(release: GithubRelease) -> Self {
+        // the upload url is on another host (uploads.github.com)
+        let reference = match release.upload_url.split_once('{') {
+            Some((upload_url, _)) => upload_url.to_string(),
+            None => release.upload_url,
+        };
+        Release {
+            name: release.name.unwrap_or_else(|| release.tag_name.clone()),
+            tag_name: release.tag_name,
+            body: release.body.unwrap_or_default(),
+            draft: release.draft,
+            prerelease: release.prerelease,
+            assets: release.assets.into_iter().map(|a| a.into()).collect(),
+            reference,
+        }
+    }
+}
+
+impl From<Release> for GithubReleaseCreation {
+    fn from(release: Release) -> Self {
+        GithubReleaseCreation {
+            tag_name: release.tag_name,
+            name: release.name,
+            body: release.body,
+            draft: release.draft,
+            prerelease: release.prerelease,
+        }
+    }
+}
@@ -22,16 +22,134 @@ pub struct RepoGithub {
 
     /// Repository fork status
     pub fork: bool,
+
+    /// Whether the wiki is enabled
+    #[serde(default, skip_serializing)]
+    pub has_wiki: bool,
+
+    /// Whether the issues are enabled
+    #[serde(default, skip_serializing)]
+    pub has_issues: bool,
+
+    /// Whether the projects are enabled
+    #[serde(default, skip_serializing)]
+    pub has_projects: bool,
+
+    /// Repository topics
+    #[serde(default, skip_serializing)]
+    pub topics: Vec<String>,
+
+    /// Repository homepage
+    #[serde(default, skip_serializing)]
+    pub homepage: Option<String>,
+
+    /// Repository default branch
+    #[serde(default, skip_serializing)]
+    pub default_branch: String,
+
+    /// Repository archived status
+    #[serde(default, skip_serializing)]
+    pub archived: bool,
+}
+
+/// Github Repo creation and edition payload
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GithubRepoEdition {
+    /// Repository name (creation only, an edition would rename the repository)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Repository description
+    pub description: String,
+
+    /// Repository homepage
+    pub homepage: String,
+
+    /// Repository private status
+    pub private: bool,
+
+    /// Whether the issues are enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_issues: Option<bool>,
+
+    /// Whether the wiki is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_wiki: Option<bool>,
+
+    /// Whether the projects are enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_projects: Option<bool>,
+
+    /// Repository default branch (edition only, the branch must exist)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+
+    /// Repository archived status (edition only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+impl GithubRepoEdition {
+    /// Payload to create a repository (without the default branch nor the archived status)
+    pub fn creation(repo: &Repo) -> Self {
+        GithubRepoEdition {
+            // the repository is addressed by its path, like the pushes
+            name: Some(repo.path.clone()),
+            description: repo.description.clone(),
+            homepage: repo.homepage.clone(),
+            private: repo.private,
+            has_issues: repo.has_issues,
+            has_wiki: repo.has_wiki,
+            has_projects: repo.has_projects,
+            default_branch: None,
+            archived: None,
+        }
+    }
+
+    /// Payload to edit a repository
+    pub fn edition(repo: &Repo) -> Self {
+        GithubRepoEdition {
+            default_branch: Some(repo.default_branch.clone()).filter(|branch| !branch.is_empty()),
+            archived: Some(repo.archived),
+            name: None,
+            ..Self::creation(repo)
+        }
+    }
+}
+
+/// Github topics of a repository
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GithubTopics {
+    /// Topics (lowercase, with dashes instead of spaces)
+    pub names: Vec<String>,
+}
+
+impl From<&Repo> for GithubTopics {
+    fn from(repo: &Repo) -> Self {
+        GithubTopics {
+            names: repo.normalized_topics(),
+        }
+    }
 }
 
 impl From<RepoGithub> for Repo {
     fn from(repo: RepoGithub) -> Self {
+        let mut topics = repo.topics;
+        topics.sort();
         Repo {
             name: repo.name.clone(),
             path: repo.name,
             description: repo.description.unwrap_or_default(),
             private: repo.private,
             fork: repo.fork,
+            mapped_path: None,
+            topics,
+            homepage: repo.homepage.unwrap_or_default(),
+            default_branch: repo.default_branch,
+            archived: repo.archived,
+            has_issues: Some(repo.has_issues),
+            has_wiki: Some(repo.has_wiki),
+            has_projects: Some(repo.has_projects),
         }
     }
 }
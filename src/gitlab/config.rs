@@ -16,6 +16,35 @@ pub struct GitlabConfig {
 
     /// Custom Gitlab url
     pub custom_url: Option<String>,
+
+    /// Group (full path, e.g. `org/team`) owning the repositories, instead of the user
+    pub group: Option<String>,
+
+    /// Mapping of the projects of subgroups on platforms without nested namespaces
+    pub path_mapping: Option<PathMapping>,
+}
+
+/// Mapping of a nested project path (`sub/project`) to a name without nesting
+#[derive(Deserialize, Serialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathMapping {
+    /// Flatten the path relative to the group: `sub-project`
+    #[default]
+    Flatten,
+
+    /// Prefix the flattened path with the group, top-level projects included:
+    /// `org-team-sub-project`, `org-team-project`
+    Prefix,
+}
+
+impl PathMapping {
+    /// Map the path of a project relative to the group
+    pub fn map(&self, group: &str, relative_path: &str) -> String {
+        match self {
+            PathMapping::Flatten => relative_path.replace('/', "-"),
+            PathMapping::Prefix => format!("{group}/{relative_path}").replace('/', "-"),
+        }
+    }
 }
 
 impl GitlabConfig {
@@ -40,13 +69,57 @@ impl GitlabConfig {
             gitlab,
             GitlabConfig,
             custom_url,
-            "custom gitlab url - empty to get the default Gitlab url"
+            "custom gitlab url (e.g. https://git.example.com/gitlab) - empty to get the default Gitlab url"
         );
         let cust_url = if custom_url.is_empty() {
             None
         } else {
             Some(custom_url)
         };
-        Ok(GitlabPlatform::new(username, token, cust_url))
+        let (group, path_mapping) = match &config.config_data.gitlab {
+            Some(gitlab_config) => (
+                gitlab_config
+                    .group
+                    .clone()
+                    .filter(|group| !group.is_empty()),
+                gitlab_config.path_mapping.clone().unwrap_or_default(),
+            ),
+            None => (None, PathMapping::default()),
+        };
+        Ok(GitlabPlatform::new(username, token, cust_url).with_group(group, path_mapping))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gitlab::repo::GitlabRepo;
+
+    #[test]
+    fn path_mapping() -> Result<(), GitMoverError> {
+        assert_eq!(
+            PathMapping::Flatten.map("org", "sub/project"),
+            "sub-project"
+        );
+        assert_eq!(
+            PathMapping::Prefix.map("org/team", "sub/project"),
+            "org-team-sub-project"
+        );
+        let top_level = GitlabRepo {
+            name: "tool".to_string(),
+            path: "tool".to_string(),
+            path_with_namespace: "org/team/tool".to_string(),
+            ..Default::default()
+        };
+        let flattened = top_level
+            .clone()
+            .into_group_repo("org/team", &PathMapping::Flatten);
+        assert_eq!(flattened.flat_path(), "tool");
+        let prefixed = top_level.into_group_repo("org/team", &PathMapping::Prefix);
+        assert_eq!(prefixed.path, "tool");
+        assert_eq!(prefixed.flat_path(), "org-team-tool");
+        let config: GitlabConfig = toml::from_str("path_mapping = \"prefix\"")?;
+        assert_eq!(config.path_mapping, Some(PathMapping::Prefix));
+        Ok(())
     }
 }
@@ -0,0 +1,112 @@
+//! Gitlab issues, notes and labels
+use crate::issue::{normalize_color, Comment, Issue, Label};
+use serde::{Deserialize, Serialize};
+
+/// Gitlab user
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GitlabUser {
+    /// Username of the user
+    pub username: String,
+}
+
+/// Gitlab label
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GitlabLabel {
+    /// Name of the label
+    pub name: String,
+
+    /// Color of the label (with `#`)
+    pub color: String,
+
+    /// Description of the label
+    pub description: Option<String>,
+}
+
+/// Gitlab issue
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GitlabIssue {
+    /// Number of the issue in the project
+    pub iid: u64,
+
+    /// Title of the issue
+    pub title: String,
+
+    /// Description of the issue
+    pub description: Option<String>,
+
+    /// Author of the issue
+    pub author: GitlabUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// State of the issue (`opened` or `closed`)
+    pub state: String,
+
+    /// Names of the labels of the issue
+    pub labels: Vec<String>,
+}
+
+/// Gitlab issue creation
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GitlabIssueCreation {
+    /// Title of the issue
+    pub title: String,
+
+    /// Description of the issue
+    pub description: String,
+
+    /// Names of the labels of the issue, comma separated
+    pub labels: String,
+}
+
+/// Gitlab note (comment) of an issue
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GitlabNote {
+    /// Author of the note
+    pub author: GitlabUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// Body of the note
+    pub body: String,
+
+    /// Whether the note was created by Gitlab (label change, mention...)
+    #[serde(default)]
+    pub system: bool,
+}
+
+impl From<GitlabLabel> for Label {
+    fn from(label: GitlabLabel) -> Self {
+        Label {
+            name: label.name,
+            color: normalize_color(&label.color),
+            description: label.description.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<GitlabIssue> for Issue {
+    fn from(issue: GitlabIssue) -> Self {
+        Issue {
+            number: issue.iid,
+            title: issue.title,
+            body: issue.description.unwrap_or_default(),
+            author: issue.author.username,
+            created_at: issue.created_at,
+            closed: issue.state == "closed",
+            labels: issue.labels,
+        }
+    }
+}
+
+impl From<GitlabNote> for Comment {
+    fn from(note: GitlabNote) -> Self {
+        Comment {
+            author: note.author.username,
+            created_at: note.created_at,
+            body: note.body,
+        }
+    }
+}
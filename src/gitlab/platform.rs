@@ -1,17 +1,33 @@
 //! Gitlab platform implementation
 use reqwest::header::ACCEPT;
 use reqwest::header::CONTENT_TYPE;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
 use std::pin::Pin;
+use url::Url;
 use urlencoding::encode;
 
+use super::config::PathMapping;
+use super::issue::{GitlabIssue, GitlabIssueCreation, GitlabLabel, GitlabNote};
+use super::pull_request::{GitlabMergeRequest, GitlabMergeRequestCreation, GitlabMergeRequestNote};
+use super::release::{GitlabRelease, GitlabReleaseCreation, GitlabReleaseLink, GitlabUpload};
+use super::repo::GitlabGroup;
 use super::repo::GitlabRepo;
-use super::repo::GitlabRepoEdition;
+use super::repo::{access_level, GitlabRepoEdition};
 use super::GITLAB_URL;
 
+use crate::diff::metadata_changes;
 use crate::errors::GitMoverError;
+use crate::issue::{Comment, Issue, Label};
+use crate::lfs::LfsEndpoint;
 use crate::platform::Platform;
 use crate::platform::PlatformType;
-use crate::utils::Repo;
+use crate::platform::RepoField;
+use crate::pull_request::{PullRequest, PullRequestState};
+use crate::release::{Release, ReleaseAsset};
+use crate::utils::{get_host, normalize_url, Repo};
 
 /// Gitlab platform
 #[derive(Default, Debug, Clone)]
@@ -25,38 +41,183 @@ pub struct GitlabPlatform {
     /// Reqwest client
     client: reqwest::Client,
 
-    /// Custom url,
-    custom_url: Option<String>,
+    /// Instance url (with scheme and optional path prefix)
+    url: String,
+
+    /// Group owning the repositories
+    group: Option<String>,
+
+    /// Mapping of the projects of subgroups
+    path_mapping: PathMapping,
 }
 
 impl GitlabPlatform {
     /// Create a new Gitlab platform
     pub fn new(username: String, token: String, custom_url: Option<String>) -> Self {
+        let url = match custom_url {
+            Some(cust_url) => normalize_url(&cust_url),
+            None => normalize_url(GITLAB_URL),
+        };
         Self {
             username,
             token,
             client: reqwest::Client::new(),
-            custom_url,
+            url,
+            group: None,
+            path_mapping: PathMapping::default(),
+        }
+    }
+
+    /// Use a group (and its subgroups) instead of the user namespace
+    pub fn with_group(mut self, group: Option<String>, path_mapping: PathMapping) -> Self {
+        self.group = group.map(|group| group.trim_matches('/').to_string());
+        self.path_mapping = path_mapping;
+        self
+    }
+
+    /// Get the API url
+    fn api_url(&self) -> String {
+        format!("{}/api/v4", self.url)
+    }
+
+    /// Get the API url listing the projects of the group or the user
+    fn projects_url(&self) -> String {
+        match &self.group {
+            Some(group) => format!("{}/groups/{}/projects", self.api_url(), encode(group)),
+            None => format!("{}/projects", self.api_url()),
+        }
+    }
+
+    /// Get the query filtering the projects of the group or the user
+    fn projects_query(&self) -> (&'static str, &'static str) {
+        match &self.group {
+            Some(_) => ("include_subgroups", "true"),
+            None => ("owned", "true"),
+        }
+    }
+
+    /// Convert a Gitlab repo
+    fn to_repo(&self, repo: GitlabRepo) -> Repo {
+        match &self.group {
+            Some(group) => repo.into_group_repo(group, &self.path_mapping),
+            None => repo.into(),
+        }
+    }
+
+    /// Get the id of the group, used to create projects in it
+    async fn get_namespace_id(&self) -> Result<Option<u64>, GitMoverError> {
+        let Some(group) = &self.group else {
+            return Ok(None);
+        };
+        let url = format!("{}/groups/{}", self.api_url(), encode(group));
+        let response = self
+            .client
+            .get(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .header(ACCEPT, "application/json")
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let text = response.text().await?;
+            return Err(GitMoverError::new(format!(
+                "{text} for {}",
+                PlatformType::Gitlab
+            )));
+        }
+        let group: GitlabGroup = response.json().await?;
+        Ok(Some(group.id))
+    }
+
+    /// Get the API url of a project of the owner
+    fn project_url(&self, repo_path: &str) -> String {
+        let full_path = format!("{}/{}", self.get_owner(), repo_path);
+        format!("{}/projects/{}", self.api_url(), encode(&full_path))
+    }
+
+    /// Build an API request with the Gitlab headers
+    fn api_request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .header(ACCEPT, "application/json")
+    }
+
+    /// Send an API request, failing on error statuses
+    async fn send(request: RequestBuilder) -> Result<reqwest::Response, GitMoverError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await?;
+            return Err(
+                GitMoverError::new(format!("{text} for {}", PlatformType::Gitlab))
+                    .with_status(status),
+            );
+        }
+        Ok(response)
+    }
+
+    /// Send an API request and parse its JSON response
+    async fn send_json<T: DeserializeOwned + Send>(
+        request: RequestBuilder,
+    ) -> Result<T, GitMoverError> {
+        let text = Self::send(request).await?.text().await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Get all the pages of an API list
+    async fn get_pages<T: DeserializeOwned + Send>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, GitMoverError> {
+        let mut page: usize = 1;
+        let mut all_items = vec![];
+        loop {
+            let request = self
+                .api_request(Method::GET, url)
+                .query(query)
+                .query(&[("per_page", "100"), ("page", &page.to_string())]);
+            let items: Vec<T> = Self::send_json(request).await?;
+            if items.is_empty() {
+                break;
+            }
+            all_items.extend(items);
+            page += 1;
         }
+        Ok(all_items)
     }
 }
 
 impl Platform for GitlabPlatform {
     fn get_remote_url(&self) -> String {
-        match &self.custom_url {
-            Some(cust_url) => cust_url.to_string(),
-            None => GITLAB_URL.to_string(),
-        }
+        get_host(&self.url)
     }
 
     fn get_username(&self) -> &str {
         &self.username
     }
 
+    fn get_owner(&self) -> &str {
+        match &self.group {
+            Some(group) => group,
+            None => &self.username,
+        }
+    }
+
     fn get_type(&self) -> PlatformType {
         PlatformType::Gitlab
     }
 
+    fn get_repo_fields(&self) -> &'static [RepoField] {
+        // no homepage on Gitlab
+        &[
+            RepoField::Description,
+            RepoField::Private,
+            RepoField::Archived,
+            RepoField::Topics,
+        ]
+    }
+
     fn create_repo(
         &self,
         repo: Repo,
@@ -65,14 +226,21 @@ impl Platform for GitlabPlatform {
         let repo = repo.clone();
         let client = self.client.clone();
         Box::pin(async move {
-            let url = format!("https://{GITLAB_URL}/api/v4/projects");
+            let url = format!("{}/projects", self.api_url());
             let visibility = if repo.private { "private" } else { "public" };
             let json_body = GitlabRepo {
                 name: repo.name.to_string(),
                 path: repo.path.to_string(),
                 description: Some(repo.description.to_string()),
                 visibility: visibility.to_string(),
-                forked_from_project: None, // unused
+                forked_from_project: None,          // unused
+                path_with_namespace: String::new(), // unused
+                namespace_id: self.get_namespace_id().await?,
+                wiki_access_level: access_level(repo.has_wiki),
+                issues_access_level: access_level(repo.has_issues),
+                topics: repo.topics.clone(),
+                default_branch: None, // the branch doesn't exist yet
+                archived: false,      // unused
             };
             let request = client
                 .post(url)
@@ -94,8 +262,9 @@ impl Platform for GitlabPlatform {
                         )));
                     }
                 };
-                let json_body_as_repo = json_body.into();
-                if get_repo != json_body_as_repo {
+                let json_body_as_repo = self.to_repo(json_body);
+                let fields = self.get_repo_fields();
+                if !metadata_changes(&json_body_as_repo, &get_repo, fields).is_empty() {
                     return self.edit_repo(json_body_as_repo).await;
                 }
             }
@@ -111,16 +280,9 @@ impl Platform for GitlabPlatform {
         let repo = repo.clone();
         let client = self.client.clone();
         Box::pin(async move {
-            let repo_url = format!("{}/{}", self.get_username(), repo.path);
-            let url = format!(
-                "https://{}/api/v4/projects/{}",
-                GITLAB_URL,
-                encode(&repo_url),
-            );
-            let json_body = GitlabRepoEdition {
-                description: repo.description.to_string(),
-                visibility: (if repo.private { "private" } else { "public" }).to_string(),
-            };
+            let repo_url = format!("{}/{}", self.get_owner(), repo.path);
+            let url = format!("{}/projects/{}", self.api_url(), encode(&repo_url));
+            let json_body = GitlabRepoEdition::from(&repo);
             let request = client
                 .put(url)
                 .header("PRIVATE-TOKEN", &token)
@@ -137,6 +299,16 @@ impl Platform for GitlabPlatform {
                     PlatformType::Gitlab
                 )));
             }
+            let project: GitlabRepo = response.json().await?;
+            if project.archived != repo.archived {
+                let action = if repo.archived {
+                    "archive"
+                } else {
+                    "unarchive"
+                };
+                let url = format!("{}/{action}", self.project_url(&repo.path));
+                let _: Value = Self::send_json(self.api_request(Method::POST, &url)).await?;
+            }
             Ok(())
         })
     }
@@ -145,15 +317,14 @@ impl Platform for GitlabPlatform {
         &self,
         name: &str,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<Repo, GitMoverError>> + Send>> {
-        let token = self.token.clone();
+        let platform = self.clone();
         let name = name.to_string();
-        let client = self.client.clone();
         Box::pin(async move {
-            let url = format!("https://{GITLAB_URL}/api/v4/projects");
-            let request = client
-                .get(&url)
-                .header("PRIVATE-TOKEN", &token)
-                .query(&[("owned", "true"), ("search", name.as_str())])
+            let request = platform
+                .client
+                .get(platform.projects_url())
+                .header("PRIVATE-TOKEN", &platform.token)
+                .query(&[platform.projects_query(), ("search", name.as_str())])
                 .send();
             let response = request.await?;
             if !response.status().is_success() {
@@ -165,10 +336,15 @@ impl Platform for GitlabPlatform {
             }
             let text = response.text().await?;
             let repos = serde_json::from_str::<Vec<GitlabRepo>>(&text)?;
-            match repos.into_iter().next() {
-                Some(repo) => Ok(repo.into()),
+            let full_path = format!("{}/{}", platform.get_owner(), name);
+            // the search is fuzzy, only the exact path is the repository
+            match repos
+                .into_iter()
+                .find(|repo| repo.path_with_namespace == full_path)
+            {
+                Some(repo) => Ok(platform.to_repo(repo)),
                 None => Err(GitMoverError::new(format!(
-                    "{text} for {}",
+                    "Repository {full_path} not found for {}",
                     PlatformType::Gitlab
                 ))),
             }
@@ -178,10 +354,11 @@ impl Platform for GitlabPlatform {
     fn get_all_repos(
         &self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Repo>, GitMoverError>> + Send>> {
+        let platform = self.clone();
         let token = self.token.clone();
         let client = self.client.clone();
+        let url = self.projects_url();
         Box::pin(async move {
-            let url = format!("https://{GITLAB_URL}/api/v4/projects");
             let mut need_request = true;
             let mut page: usize = 1;
             let mut all_repos = vec![];
@@ -193,7 +370,7 @@ impl Platform for GitlabPlatform {
                     .query(&[
                         ("per_page", "100"),
                         ("page", &page.to_string()),
-                        ("owned", "true"),
+                        platform.projects_query(),
                     ])
                     .send();
 
@@ -210,7 +387,7 @@ impl Platform for GitlabPlatform {
                     Ok(repos) => repos,
                     Err(e) => return Err(e.into()),
                 };
-                let repos: Vec<Repo> = repos.into_iter().map(|r| r.into()).collect();
+                let repos: Vec<Repo> = repos.into_iter().map(|r| platform.to_repo(r)).collect();
                 if repos.is_empty() {
                     need_request = false;
                 }
@@ -230,12 +407,8 @@ impl Platform for GitlabPlatform {
         let name = name.to_string();
         let client = self.client.clone();
         Box::pin(async move {
-            let repo_url = format!("{}/{}", self.get_username(), name);
-            let url = format!(
-                "https://{}/api/v4/projects/{}",
-                GITLAB_URL,
-                encode(&repo_url),
-            );
+            let repo_url = format!("{}/{}", self.get_owner(), name);
+            let url = format!("{}/projects/{}", self.api_url(), encode(&repo_url));
             let request = client
                 .delete(&url)
                 .header("PRIVATE-TOKEN", &token)
@@ -254,4 +427,662 @@ impl Platform for GitlabPlatform {
             Ok(())
         })
     }
+
+    fn get_labels(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Label>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/labels", self.project_url(repo_path));
+        Box::pin(async move {
+            let labels: Vec<GitlabLabel> = self.get_pages(&url, &[]).await?;
+            Ok(labels.into_iter().map(|l| l.into()).collect())
+        })
+    }
+
+    fn create_label(
+        &self,
+        repo_path: &str,
+        label: Label,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = format!("{}/labels", self.project_url(repo_path));
+        Box::pin(async move {
+            let json_body = GitlabLabel {
+                name: label.name,
+                color: format!("#{}", label.color),
+                description: Some(label.description),
+            };
+            let request = self.api_request(Method::POST, &url).json(&json_body);
+            let _: Value = Self::send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn get_issues(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Issue>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/issues", self.project_url(repo_path));
+        Box::pin(async move {
+            let issues: Vec<GitlabIssue> = self
+                .get_pages(&url, &[("scope", "all"), ("sort", "asc")])
+                .await?;
+            Ok(issues.into_iter().map(|issue| issue.into()).collect())
+        })
+    }
+
+    fn create_issue(
+        &self,
+        repo_path: &str,
+        issue: Issue,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<u64, GitMoverError>> + Send + '_>> {
+        let url = format!("{}/issues", self.project_url(repo_path));
+        Box::pin(async move {
+            let json_body = GitlabIssueCreation {
+                title: issue.title,
+                description: issue.body,
+                labels: issue.labels.join(","),
+            };
+            let request = self
+                .api_request(Method::POST, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body);
+            let created: GitlabIssue = Self::send_json(request).await?;
+            if issue.closed {
+                let url = format!("{url}/{}", created.iid);
+                let request = self
+                    .api_request(Method::PUT, &url)
+                    .json(&json!({ "state_event": "close" }));
+                let _: Value = Self::send_json(request).await?;
+            }
+            Ok(created.iid)
+        })
+    }
+
+    fn get_issue_comments(
+        &self,
+        repo_path: &str,
+        number: u64,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Comment>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/issues/{number}/notes", self.project_url(repo_path));
+        Box::pin(async move {
+            let notes: Vec<GitlabNote> = self.get_pages(&url, &[("sort", "asc")]).await?;
+            Ok(notes
+                .into_iter()
+                .filter(|note| !note.system)
+                .map(|note| note.into())
+                .collect())
+        })
+    }
+
+    fn create_issue_comment(
+        &self,
+        repo_path: &str,
+        number: u64,
+        comment: Comment,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = format!("{}/issues/{number}/notes", self.project_url(repo_path));
+        Box::pin(async move {
+            let request = self
+                .api_request(Method::POST, &url)
+                .json(&json!({ "body": comment.body }));
+            let _: Value = Self::send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn get_pull_requests(
+        &self,
+        repo_path: &str,
+    ) -> Pin<
+        Box<dyn std::future::Future<Output = Result<Vec<PullRequest>, GitMoverError>> + Send + '_>,
+    > {
+        let url = format!("{}/merge_requests", self.project_url(repo_path));
+        Box::pin(async move {
+            let merge_requests: Vec<GitlabMergeRequest> = self
+                .get_pages(&url, &[("scope", "all"), ("state", "all"), ("sort", "asc")])
+                .await?;
+            Ok(merge_requests.into_iter().map(|mr| mr.into()).collect())
+        })
+    }
+
+    fn create_pull_request(
+        &self,
+        repo_path: &str,
+        pull_request: PullRequest,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<u64, GitMoverError>> + Send + '_>> {
+        let url = format!("{}/merge_requests", self.project_url(repo_path));
+        Box::pin(async move {
+            let json_body = GitlabMergeRequestCreation {
+                title: pull_request.title,
+                description: pull_request.body,
+                source_branch: pull_request.source_branch,
+                target_branch: pull_request.target_branch,
+            };
+            let request = self
+                .api_request(Method::POST, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body);
+            let created: GitlabMergeRequest = Self::send_json(request).await?;
+            if pull_request.state != PullRequestState::Open {
+                let url = format!("{url}/{}", created.iid);
+                let request = self
+                    .api_request(Method::PUT, &url)
+                    .json(&json!({ "state_event": "close" }));
+                let _: Value = Self::send_json(request).await?;
+            }
+            Ok(created.iid)
+        })
+    }
+
+    fn get_pull_request_comments(
+        &self,
+        repo_path: &str,
+        number: u64,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Comment>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!(
+            "{}/merge_requests/{number}/notes",
+            self.project_url(repo_path)
+        );
+        Box::pin(async move {
+            let notes: Vec<GitlabMergeRequestNote> =
+                self.get_pages(&url, &[("sort", "asc")]).await?;
+            Ok(notes
+                .into_iter()
+                .filter(|note| !note.system)
+                .map(|note| note.into())
+                .collect())
+        })
+    }
+
+    fn create_pull_request_comment(
+        &self,
+        repo_path: &str,
+        number: u64,
+        comment: Comment,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = format!(
+            "{}/merge_requests/{number}/notes",
+            self.project_url(repo_path)
+        );
+        Box::pin(async move {
+            let request = self
+                .api_request(Method::POST, &url)
+                .json(&json!({ "body": comment.body }));
+            let _: Value = Self::send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn get_releases(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Release>, GitMoverError>> + Send + '_>>
+    {
+        let url = format!("{}/releases", self.project_url(repo_path));
+        Box::pin(async move {
+            let releases: Vec<GitlabRelease> = self.get_pages(&url, &[]).await?;
+            Ok(releases.into_iter().map(|r| r.into()).collect())
+        })
+    }
+
+    fn create_release(
+        &self,
+        repo_path: &str,
+        release: Release,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<String, GitMoverError>> + Send + '_>> {
+        let url = format!("{}/releases", self.project_url(repo_path));
+        Box::pin(async move {
+            let json_body: GitlabReleaseCreation = release.into();
+            let request = self
+                .api_request(Method::POST, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&json_body);
+            let created: GitlabRelease = Self::send_json(request).await?;
+            Ok(created.tag_name)
+        })
+    }
+
+    fn download_release_asset(
+        &self,
+        _repo_path: &str,
+        asset: ReleaseAsset,
+    ) -> Pin<
+        Box<dyn std::future::Future<Output = Result<reqwest::Response, GitMoverError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut request = self.client.get(&asset.download_url);
+            // the links of a release can point anywhere, the token is only sent to the instance,
+            // as a bearer token that reqwest drops when redirected to another host
+            let origin = |url: &str| Url::parse(url).ok().map(|url| url.origin());
+            if origin(&asset.download_url) == origin(&self.url) {
+                request = request.bearer_auth(&self.token);
+            }
+            Self::send(request).await
+        })
+    }
+
+    fn upload_release_asset(
+        &self,
+        repo_path: &str,
+        release_reference: &str,
+        asset: ReleaseAsset,
+        content: reqwest::Body,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let upload_url = format!("{}/uploads", self.project_url(repo_path));
+        let links_url = format!(
+            "{}/releases/{}/assets/links",
+            self.project_url(repo_path),
+            encode(release_reference)
+        );
+        Box::pin(async move {
+            // the file is uploaded to the project, then linked to the release
+            let part = match asset.size {
+                0 => Part::stream(content),
+                size => Part::stream_with_length(content, size),
+            }
+            .file_name(asset.name.clone())
+            .mime_str(&asset.content_type)?;
+            let request = self
+                .api_request(Method::POST, &upload_url)
+                .multipart(Form::new().part("file", part));
+            let upload: GitlabUpload = Self::send_json(request).await?;
+            let json_body = GitlabReleaseLink {
+                name: asset.name,
+                url: format!("{}{}", self.url, upload.full_path),
+            };
+            let request = self.api_request(Method::POST, &links_url).json(&json_body);
+            let _: Value = Self::send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn get_lfs_endpoint(&self, repo_path: &str) -> Result<LfsEndpoint, GitMoverError> {
+        Ok(LfsEndpoint {
+            url: format!(
+                "{}/{}/{}.git/info/lfs",
+                self.url,
+                self.get_owner(),
+                repo_path
+            ),
+            username: self.username.clone(),
+            token: self.token.clone(),
+        })
+    }
+
+    fn set_default_branch(
+        &self,
+        repo_path: &str,
+        branch: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = self.project_url(repo_path);
+        let json_body = json!({ "default_branch": branch });
+        Box::pin(async move {
+            let request = self.api_request(Method::PUT, &url).json(&json_body);
+            let _: Value = Self::send_json(request).await?;
+            Ok(())
+        })
+    }
+
+    fn has_wiki(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<bool, GitMoverError>> + Send + '_>> {
+        let url = self.project_url(repo_path);
+        Box::pin(async move {
+            let project: GitlabRepo = Self::send_json(self.api_request(Method::GET, &url)).await?;
+            Ok(project
+                .wiki_access_level
+                .is_some_and(|level| level != "disabled"))
+        })
+    }
+
+    fn enable_wiki(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let url = self.project_url(repo_path);
+        Box::pin(async move {
+            let request = self
+                .api_request(Method::PUT, &url)
+                .json(&json!({ "wiki_access_level": "enabled" }));
+            let _: Value = Self::send_json(request).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Create a Gitlab platform pointing to the mock server, with a path prefix
+    fn mock_platform(server: &MockServer) -> GitlabPlatform {
+        GitlabPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(format!("{}/gitlab/", server.uri())),
+        )
+    }
+
+    #[test]
+    fn custom_url_remote() {
+        let platform = GitlabPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some("https://git.example.com/gitlab".to_string()),
+        );
+        assert_eq!(platform.get_remote_url(), "git.example.com");
+        assert_eq!(platform.api_url(), "https://git.example.com/gitlab/api/v4");
+        let platform = GitlabPlatform::new("user".to_string(), "token".to_string(), None);
+        assert_eq!(platform.get_remote_url(), GITLAB_URL);
+        assert_eq!(platform.api_url(), "https://gitlab.com/api/v4");
+    }
+
+    #[tokio::test]
+    async fn custom_url_get_all_repos() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/projects"))
+            .and(header("PRIVATE-TOKEN", "token"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "Repo", "path": "repo", "description": "desc", "visibility": "public"}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/projects"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let repos = mock_platform(&server).get_all_repos().await?;
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].path, "repo");
+        assert_eq!(repos[0].description, "desc");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn custom_url_create_repo() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/gitlab/api/v4/projects"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            ..Default::default()
+        };
+        mock_platform(&server).create_repo(repo).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn custom_url_edit_and_delete_repo() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/gitlab/api/v4/projects/user%2Frepo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!(
+                {"name": "repo", "path": "repo", "description": null, "visibility": "public", "archived": false}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/gitlab/api/v4/projects/user%2Frepo"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = mock_platform(&server);
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            ..Default::default()
+        };
+        platform.edit_repo(repo).await?;
+        platform.delete_repo("repo").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn custom_url_get_repo() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/projects"))
+            .and(query_param("search", "repo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "repo-old", "path": "repo-old", "path_with_namespace": "user/repo-old", "description": null, "visibility": "public"},
+                {"name": "repo", "path": "repo", "path_with_namespace": "user/repo", "description": null, "visibility": "private"}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/projects"))
+            .and(query_param("search", "rep"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "repo", "path": "repo", "path_with_namespace": "user/repo", "description": null, "visibility": "private"}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = mock_platform(&server);
+        let repo = platform.get_repo("repo").await?;
+        assert_eq!(repo.path, "repo");
+        assert!(repo.private);
+        // a fuzzy match is not the repository
+        assert!(platform.get_repo("rep").await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn group_get_all_repos_and_create_repo() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/groups/org%2Fteam/projects"))
+            .and(query_param("include_subgroups", "true"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "tool", "path": "tool", "path_with_namespace": "org/team/tool", "description": null, "visibility": "public"},
+                {"name": "lib", "path": "lib", "path_with_namespace": "org/team/sub/lib", "description": null, "visibility": "private"}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/groups/org%2Fteam/projects"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/groups/org%2Fteam"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 42})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/gitlab/api/v4/projects"))
+            .and(body_partial_json(
+                serde_json::json!({"path": "tool", "namespace_id": 42}),
+            ))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform =
+            mock_platform(&server).with_group(Some("/org/team/".to_string()), PathMapping::Flatten);
+        assert_eq!(platform.get_owner(), "org/team");
+        let repos = platform.get_all_repos().await?;
+        assert_eq!(repos[0].name, "tool");
+        assert_eq!(repos[0].path, "tool");
+        assert_eq!(repos[1].name, "sub-lib");
+        assert_eq!(repos[1].path, "sub/lib");
+        assert_eq!(repos[1].flat_path(), "sub-lib");
+        platform.create_repo(repos[0].clone()).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn issues() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/projects/user%2Frepo/issues/3/notes"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"author": {"username": "alice"}, "created_at": "2024-01-01T00:00:00Z", "body": "added ~bug label", "system": true},
+                {"author": {"username": "bob"}, "created_at": "2024-01-02T00:00:00Z", "body": "Same here", "system": false}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/projects/user%2Frepo/issues/3/notes"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/gitlab/api/v4/projects/user%2Frepo/issues"))
+            .and(body_partial_json(serde_json::json!({"title": "Bug", "labels": "bug,ui"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!(
+                {"iid": 4, "title": "Bug", "description": null, "author": {"username": "user"}, "created_at": "2024-02-01T00:00:00Z", "state": "opened", "labels": ["bug", "ui"]}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/gitlab/api/v4/projects/user%2Frepo/issues/4"))
+            .and(body_partial_json(
+                serde_json::json!({"state_event": "close"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = mock_platform(&server);
+        let comments = platform.get_issue_comments("repo", 3).await?;
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].author, "bob");
+        let issue = Issue {
+            title: "Bug".to_string(),
+            closed: true,
+            labels: vec!["bug".to_string(), "ui".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(platform.create_issue("repo", issue).await?, 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn upload_release_asset() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/gitlab/api/v4/projects/user%2Frepo/uploads"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!(
+                {"full_path": "/-/project/1/uploads/abc/app.zip"}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/gitlab/api/v4/projects/user%2Frepo/releases/v1.0/assets/links",
+            ))
+            .and(body_partial_json(serde_json::json!({
+                "name": "app.zip",
+                "url": format!("{}/gitlab/-/project/1/uploads/abc/app.zip", server.uri())
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let asset = ReleaseAsset {
+            name: "app.zip".to_string(),
+            size: 3,
+            content_type: "application/zip".to_string(),
+            ..Default::default()
+        };
+        mock_platform(&server)
+            .upload_release_asset("repo", "v1.0", asset, reqwest::Body::from("zip"))
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn download_release_asset() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        let external = MockServer::start().await;
+        for mock_server in [&server, &external] {
+            Mock::given(method("GET"))
+                .and(path("/app.zip"))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes("zip"))
+                .expect(1)
+                .mount(mock_server)
+                .await;
+        }
+        let platform = mock_platform(&server);
+        for mock_server in [&server, &external] {
+            let asset = ReleaseAsset {
+                name: "app.zip".to_string(),
+                download_url: format!("{}/app.zip", mock_server.uri()),
+                ..Default::default()
+            };
+            platform.download_release_asset("repo", asset).await?;
+        }
+        let authorization = |requests: Vec<wiremock::Request>| {
+            requests
+                .iter()
+                .any(|request| request.headers.contains_key("authorization"))
+        };
+        assert!(authorization(
+            server.received_requests().await.unwrap_or_default()
+        ));
+        let external_requests = external.received_requests().await.unwrap_or_default();
+        assert!(!authorization(external_requests.clone()));
+        assert!(!external_requests
+            .iter()
+            .any(|request| request.headers.contains_key("private-token")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn wiki() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gitlab/api/v4/projects/user%2Frepo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!(
+                {"name": "repo", "path": "repo", "description": null, "visibility": "public", "wiki_access_level": "disabled"}
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/gitlab/api/v4/projects/user%2Frepo"))
+            .and(body_partial_json(
+                serde_json::json!({"wiki_access_level": "enabled"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = mock_platform(&server);
+        assert!(!platform.has_wiki("repo").await?);
+        platform.enable_wiki("repo").await?;
+        Ok(())
+    }
 }
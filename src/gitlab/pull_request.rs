@@ -0,0 +1,115 @@
+//! Gitlab merge requests and their notes
+use super::issue::GitlabUser;
+use crate::issue::Comment;
+use crate::pull_request::{with_file_reference, PullRequest, PullRequestState};
+use serde::{Deserialize, Serialize};
+
+/// Gitlab merge request
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GitlabMergeRequest {
+    /// Number of the merge request in the project
+    pub iid: u64,
+
+    /// Title of the merge request
+    pub title: String,
+
+    /// Description of the merge request
+    pub description: Option<String>,
+
+    /// Author of the merge request
+    pub author: GitlabUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// State of the merge request (`opened`, `closed`, `locked` or `merged`)
+    pub state: String,
+
+    /// Branch to merge
+    pub source_branch: String,
+
+    /// Branch to merge into
+    pub target_branch: String,
+}
+
+/// Gitlab merge request creation
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GitlabMergeRequestCreation {
+    /// Title of the merge request
+    pub title: String,
+
+    /// Description of the merge request
+    pub description: String,
+
+    /// Branch to merge
+    pub source_branch: String,
+
+    /// Branch to merge into
+    pub target_branch: String,
+}
+
+/// Position of a note on the diff
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GitlabNotePosition {
+    /// File of the note
+    pub new_path: String,
+
+    /// Line of the note
+    pub new_line: Option<u64>,
+}
+
+/// Gitlab note of a merge request
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GitlabMergeRequestNote {
+    /// Author of the note
+    pub author: GitlabUser,
+
+    /// Creation date
+    pub created_at: String,
+
+    /// Body of the note
+    pub body: String,
+
+    /// Whether the note was created by Gitlab (new commits, approvals...)
+    #[serde(default)]
+    pub system: bool,
+
+    /// Position of the note, for review comments on the diff
+    pub position: Option<GitlabNotePosition>,
+}
+
+impl From<GitlabMergeRequest> for PullRequest {
+    fn from(merge_request: GitlabMergeRequest) -> Self {
+        let state = match merge_request.state.as_str() {
+            "merged" => PullRequestState::Merged,
+            "opened" => PullRequestState::Open,
+            _ => PullRequestState::Closed,
+        };
+        PullRequest {
+            number: merge_request.iid,
+            title: merge_request.title,
+            body: merge_request.description.unwrap_or_default(),
+            author: merge_request.author.username,
+            created_at: merge_request.created_at,
+            source_branch: merge_request.source_branch,
+            target_branch: merge_request.target_branch,
+            state,
+        }
+    }
+}
+
+impl From<GitlabMergeRequestNote> for Comment {
+    fn from(note: GitlabMergeRequestNote) -> Self {
+        let body = match note.position {
+            Some(position) => {
+                with_file_reference(&position.new_path, position.new_line, &note.body)
+            }
+            None => note.body,
+        };
+        Comment {
+            author: note.author.username,
+            created_at: note.created_at,
+            body,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Gitlab releases and release links
+use crate::release::{Release, ReleaseAsset, DEFAULT_CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+
+/// Gitlab release link (asset)
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GitlabReleaseLink {
+    /// Name of the link
+    pub name: String,
+
+    /// Url of the link
+    pub url: String,
+}
+
+/// Gitlab release assets
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GitlabReleaseAssets {
+    /// Links of the release (the generated source archives are ignored)
+    #[serde(default)]
+    pub links: Vec<GitlabReleaseLink>,
+}
+
+/// Gitlab release
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GitlabRelease {
+    /// Tag of the release
+    pub tag_name: String,
+
+    /// Name of the release
+    pub name: Option<String>,
+
+    /// Notes of the release
+    pub description: Option<String>,
+
+    /// Assets of the release
+    #[serde(default)]
+    pub assets: GitlabReleaseAssets,
+}
+
+/// Gitlab release creation (Gitlab has no draft nor prerelease)
+#[derive(Serialize, Default, Debug, Clone)]
+pub struct GitlabReleaseCreation {
+    /// Tag of the release
+    pub tag_name: String,
+
+    /// Name of the release
+    pub name: String,
+
+    /// Notes of the release
+    pub description: String,
+}
+
+/// Gitlab uploaded file
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct GitlabUpload {
+    /// Path of the file on the instance
+    pub full_path: String,
+}
+
+impl From<GitlabRelease> for Release {
+    fn from(release: GitlabRelease) -> Self {
+        Release {
+            name: release.name.unwrap_or_else(|| release.tag_name.clone()),
+            reference: release.tag_name.clone(),
+            tag_name: release.tag_name,
+            body: release.description.unwrap_or_default(),
+            draft: false,
+            prerelease: false,
+            assets: release
+                .assets
+                .links
+                .into_iter()
+                .map(|link| ReleaseAsset {
+                    name: link.name,
+                    size: 0,
+                    content_type: DEFAULT_CONTENT_TYPE.to_string(),
+                    download_url: link.url,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Release> for GitlabReleaseCreation {
+    fn from(release: Release) -> Self {
+        GitlabReleaseCreation {
+            tag_name: release.tag_name,
+            name: release.name,
+            description: release.body,
+        }
+    }
+}
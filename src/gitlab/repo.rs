@@ -1,4 +1,5 @@
 //! Gitlab Repo module
+use super::config::PathMapping;
 use crate::utils::Repo;
 use serde::{Deserialize, Serialize};
 
@@ -20,14 +21,51 @@ pub struct GitlabRepo {
     /// Forked from project
     #[serde(skip_serializing)]
     pub forked_from_project: Option<ForkRepo>,
+
+    /// Repo path with its namespace
+    #[serde(default, skip_serializing)]
+    pub path_with_namespace: String,
+
+    /// Namespace to create the repo in
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<u64>,
+
+    /// Access level of the wiki (`disabled`, `private` or `enabled`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wiki_access_level: Option<String>,
+
+    /// Access level of the issues (`disabled`, `private` or `enabled`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issues_access_level: Option<String>,
+
+    /// Repo topics
+    #[serde(default)]
+    pub topics: Vec<String>,
+
+    /// Repo default branch
+    #[serde(default, skip_serializing)]
+    pub default_branch: Option<String>,
+
+    /// Repo archived status
+    #[serde(default, skip_serializing)]
+    pub archived: bool,
 }
 
+/// Gitlab Group
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct GitlabGroup {
+    /// Group id
+    pub id: u64,
+}
+
+/// Gitlab fork information
 #[derive(Deserialize, Serialize, Default, Debug, Clone)]
 pub struct ForkRepo {
     /// Forked from project id
     pub id: u64,
 }
 
+/// Gitlab Repo edition payload
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GitlabRepoEdition {
     /// Repo name
@@ -35,6 +73,64 @@ pub struct GitlabRepoEdition {
 
     /// Repo visibility
     pub visibility: String,
+
+    /// Repo topics
+    pub topics: Vec<String>,
+
+    /// Access level of the wiki
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wiki_access_level: Option<String>,
+
+    /// Access level of the issues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issues_access_level: Option<String>,
+
+    /// Repo default branch (the branch must exist)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+}
+
+/// Access level of a feature from its status (`None` if unknown)
+pub fn access_level(enabled: Option<bool>) -> Option<String> {
+    enabled.map(|enabled| (if enabled { "enabled" } else { "disabled" }).to_string())
+}
+
+/// Status of a feature from its access level (`None` if unknown)
+pub fn is_enabled(access_level: &Option<String>) -> Option<bool> {
+    access_level.as_ref().map(|level| level != "disabled")
+}
+
+impl From<&Repo> for GitlabRepoEdition {
+    fn from(repo: &Repo) -> Self {
+        GitlabRepoEdition {
+            description: repo.description.to_string(),
+            visibility: (if repo.private { "private" } else { "public" }).to_string(),
+            topics: repo.topics.clone(),
+            wiki_access_level: access_level(repo.has_wiki),
+            issues_access_level: access_level(repo.has_issues),
+            default_branch: Some(repo.default_branch.clone()).filter(|branch| !branch.is_empty()),
+        }
+    }
+}
+
+impl GitlabRepo {
+    /// Convert a repo of a group, with its path relative to the group
+    /// and its name and path mapped for platforms without nested namespaces
+    pub fn into_group_repo(self, group: &str, mapping: &PathMapping) -> Repo {
+        let relative_path = self
+            .path_with_namespace
+            .strip_prefix(&format!("{group}/"))
+            .unwrap_or(&self.path)
+            .to_string();
+        let mut repo: Repo = self.into();
+        let mapped_path = mapping.map(group, &relative_path);
+        if mapped_path != relative_path {
+            repo.name = mapped_path.clone();
+            repo.mapped_path = Some(mapped_path);
+        }
+        repo.path = relative_path;
+        repo
+    }
 }
 
 impl From<GitlabRepo> for Repo {
@@ -45,6 +141,18 @@ impl From<GitlabRepo> for Repo {
             description: repo.description.unwrap_or_default(),
             private: repo.visibility == "private",
             fork: repo.forked_from_project.is_some(),
+            mapped_path: None,
+            topics: {
+                let mut topics = repo.topics;
+                topics.sort();
+                topics
+            },
+            homepage: String::new(), // no homepage on Gitlab
+            default_branch: repo.default_branch.unwrap_or_default(),
+            archived: repo.archived,
+            has_issues: is_enabled(&repo.issues_access_level),
+            has_wiki: is_enabled(&repo.wiki_access_level),
+            has_projects: None, // no projects on Gitlab
         }
     }
 }
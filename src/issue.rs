@@ -0,0 +1,325 @@
+//! Issues, comments and labels of a repository
+use crate::{errors::GitMoverError, platform::Platform};
+
+/// Label of a repository
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Label {
+    /// Name of the label
+    pub name: String,
+
+    /// Color of the label (hexadecimal, without `#`)
+    pub color: String,
+
+    /// Description of the label
+    pub description: String,
+}
+
+/// Comment of an issue
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Comment {
+    /// Author of the comment
+    pub author: String,
+
+    /// Creation date of the comment
+    pub created_at: String,
+
+    /// Body of the comment
+    pub body: String,
+}
+
+/// Issue of a repository
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Issue {
+    /// Number of the issue in the repository
+    pub number: u64,
+
+    /// Title of the issue
+    pub title: String,
+
+    /// Body of the issue
+    pub body: String,
+
+    /// Author of the issue
+    pub author: String,
+
+    /// Creation date of the issue
+    pub created_at: String,
+
+    /// Whether the issue is closed
+    pub closed: bool,
+
+    /// Names of the labels of the issue
+    pub labels: Vec<String>,
+}
+
+/// Normalize a label color to hexadecimal without `#`
+pub(crate) fn normalize_color(color: &str) -> String {
+    color.trim_start_matches('#').to_lowercase()
+}
+
+/// Prefix a body with its original author and date,
+/// the author being formatted as code to not mention someone else on the destination
+pub(crate) fn with_original_note(verb: &str, author: &str, created_at: &str, body: &str) -> String {
+    let note = format!("_Originally {verb} by `{author}` on {created_at}_");
+    if body.is_empty() {
+        note
+    } else {
+        format!("{note}\n\n{body}")
+    }
+}
+
+/// Hidden marker of the source issue in the body of a migrated issue
+fn source_marker(number: u64) -> String {
+    format!("<!-- git-mover: source issue #{number} -->")
+}
+
+/// Migrate the labels, issues and comments of a repository,
+/// skipping the issues already migrated (same source number) to the destination
+/// but posting their missing comments (e.g. after an interrupted sync)
+/// # Errors
+/// Error if a platform doesn't support issues or if a request fails
+pub(crate) async fn sync_issues(
+    source_platform: &dyn Platform,
+    destination_platform: &dyn Platform,
+    source_path: &str,
+    destination_path: &str,
+) -> Result<usize, GitMoverError> {
+    let destination_labels = destination_platform.get_labels(destination_path).await?;
+    for label in source_platform.get_labels(source_path).await? {
+        if !destination_labels.iter().any(|l| l.name == label.name) {
+            destination_platform
+                .create_label(destination_path, label)
+                .await?;
+        }
+    }
+    let destination_issues = destination_platform.get_issues(destination_path).await?;
+    let mut source_issues = source_platform.get_issues(source_path).await?;
+    source_issues.sort_by_key(|issue| issue.number);
+    let mut count = 0;
+    for issue in source_issues {
+        let marker = source_marker(issue.number);
+        let migrated = destination_issues
+            .iter()
+            .find(|i| i.body.contains(&marker))
+            .map(|i| i.number);
+        let comments = source_platform
+            .get_issue_comments(source_path, issue.number)
+            .await?;
+        // the comments are posted in order after the issue: the first ones are already there
+        let (number, posted) = match migrated {
+            Some(number) => {
+                let posted = destination_platform
+                    .get_issue_comments(destination_path, number)
+                    .await?
+                    .len();
+                (number, posted)
+            }
+            None => {
+                let body =
+                    with_original_note("created", &issue.author, &issue.created_at, &issue.body);
+                let new_issue = Issue {
+                    body: format!("{body}\n\n{marker}"),
+                    ..issue
+                };
+                let number = destination_platform
+                    .create_issue(destination_path, new_issue)
+                    .await?;
+                count += 1;
+                (number, 0)
+            }
+        };
+        for comment in comments.into_iter().skip(posted) {
+            let new_comment = Comment {
+                body: with_original_note(
+                    "posted",
+                    &comment.author,
+                    &comment.created_at,
+                    &comment.body,
+                ),
+                ..comment
+            };
+            destination_platform
+                .create_issue_comment(destination_path, number, new_comment)
+                .await?;
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        gitea::platform::GiteaPlatform, github::platform::GithubPlatform, platform::PlatformType,
+    };
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Mount a paginated list, the second page being empty
+    async fn mount_list(server: &MockServer, list_path: &str, items: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn github_to_gitea() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        mount_list(
+            &server,
+            "/api/v3/repos/user/repo/labels",
+            json!([{"name": "bug", "color": "d73a4a", "description": "Broken"}]),
+        )
+        .await;
+        mount_list(
+            &server,
+            "/api/v3/repos/user/repo/issues",
+            json!([
+                {"number": 1, "title": "Old", "body": null, "user": {"login": "alice"}, "created_at": "2023-01-01", "state": "closed", "labels": []},
+                {"number": 2, "title": "Bug", "body": "It fails", "user": {"login": "alice"}, "created_at": "2024-01-01", "state": "open", "labels": [{"name": "bug", "color": "d73a4a", "description": null}]},
+                {"number": 3, "title": "Crash", "body": null, "user": {"login": "bob"}, "created_at": "2024-02-01", "state": "open", "labels": [{"name": "bug", "color": "d73a4a", "description": null}]}
+            ]),
+        )
+        .await;
+        mount_list(
+            &server,
+            "/api/v3/repos/user/repo/issues/2/comments",
+            json!([{"user": {"login": "bob"}, "created_at": "2024-01-02", "body": "Same"}]),
+        )
+        .await;
+        mount_list(
+            &server,
+            "/api/v3/repos/user/repo/issues/3/comments",
+            json!([]),
+        )
+        .await;
+        // the migration of the first issue was interrupted after its first comment
+        mount_list(
+            &server,
+            "/api/v3/repos/user/repo/issues/1/comments",
+            json!([
+                {"user": {"login": "bob"}, "created_at": "2023-01-02", "body": "Posted"},
+                {"user": {"login": "carol"}, "created_at": "2023-01-03", "body": "Missing"}
+            ]),
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/issues/1/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"user": {"login": "user"}, "created_at": "2023-06-02", "body": "_Originally posted by `bob` on 2023-01-02_\n\nPosted"}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/issues/1/comments"))
+            .and(body_partial_json(json!({
+                "body": "_Originally posted by `carol` on 2023-01-03_\n\nMissing"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        mount_list(&server, "/api/v1/repos/user/repo/labels", json!([])).await;
+        mount_list(
+            &server,
+            "/api/v1/repos/user/repo/issues",
+            json!([
+                {"number": 1, "title": "Old", "body": "<!-- git-mover: source issue #1 -->", "user": {"login": "user"}, "created_at": "2023-06-01", "state": "closed"},
+                {"number": 2, "title": "Bug", "body": "Another bug with the same title", "user": {"login": "user"}, "created_at": "2023-07-01", "state": "open"}
+            ]),
+        )
+        .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/labels"))
+            .and(body_partial_json(
+                json!({"name": "bug", "color": "#d73a4a"}),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/issues"))
+            .and(body_partial_json(json!({
+                "title": "Bug",
+                "body": "_Originally created by `alice` on 2024-01-01_\n\nIt fails\n\n<!-- git-mover: source issue #2 -->"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!(
+                {"number": 2, "title": "Bug", "user": {"login": "user"}, "created_at": "2024-06-01", "state": "open"}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/issues"))
+            .and(body_partial_json(json!({"title": "Crash"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!(
+                {"number": 3, "title": "Crash", "user": {"login": "user"}, "created_at": "2024-06-01", "state": "open"}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/issues/2/comments"))
+            .and(body_partial_json(json!({
+                "body": "_Originally posted by `bob` on 2024-01-02_\n\nSame"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let source = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let destination = GiteaPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            &server.uri(),
+            None,
+            None,
+            PlatformType::Gitea,
+        );
+        let count = sync_issues(&source, &destination, "repo", "repo").await?;
+        assert_eq!(count, 2);
+        // the labels are fetched once to sync them and once to create the issues
+        let requests = server.received_requests().await.unwrap_or_default();
+        let label_requests = requests
+            .iter()
+            .filter(|r| r.method == wiremock::http::Method::GET)
+            .filter(|r| r.url.path() == "/api/v1/repos/user/repo/labels")
+            .filter(|r| {
+                r.url
+                    .query_pairs()
+                    .any(|(key, value)| key == "page" && value == "1")
+            })
+            .count();
+        assert_eq!(label_requests, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn original_note() {
+        assert_eq!(
+            with_original_note("created", "alice", "2024-01-02T03:04:05Z", "Hello"),
+            "_Originally created by `alice` on 2024-01-02T03:04:05Z_\n\nHello"
+        );
+        assert_eq!(
+            with_original_note("posted", "bob", "2024-01-02", ""),
+            "_Originally posted by `bob` on 2024-01-02_"
+        );
+        assert_eq!(normalize_color("#FF0000"), "ff0000");
+    }
+}
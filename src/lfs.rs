@@ -0,0 +1,464 @@
+//! Git LFS objects of a repository, migrated with the LFS batch API
+use std::collections::{HashMap, HashSet};
+
+use reqwest::header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::{Method, RequestBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::GitMoverError;
+
+/// Media type of the LFS API
+const LFS_MEDIA_TYPE: &str = "application/vnd.git-lfs+json";
+
+/// First line of a LFS pointer file
+const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+
+/// Pointer files are small, bigger blobs are not read
+const LFS_POINTER_MAX_SIZE: usize = 1024;
+
+/// Maximum number of objects in a batch request
+const LFS_BATCH_SIZE: usize = 100;
+
+/// LFS object, identified by the sha256 of its content
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LfsObject {
+    /// Sha256 of the content
+    pub oid: String,
+
+    /// Size of the content in bytes
+    pub size: u64,
+}
+
+/// LFS server of a repository
+#[derive(Debug, Clone)]
+pub struct LfsEndpoint {
+    /// Url of the LFS server (`.../<repo>.git/info/lfs`)
+    pub url: String,
+
+    /// Username to authenticate
+    pub username: String,
+
+    /// Token to authenticate
+    pub token: String,
+}
+
+/// LFS batch request
+#[derive(Serialize, Debug)]
+struct LfsBatchRequest<'a> {
+    /// `download` or `upload`
+    operation: &'a str,
+
+    /// Transfer adapters supported by the client
+    transfers: [&'a str; 1],
+
+    /// Objects to transfer
+    objects: &'a [LfsObject],
+}
+
+/// LFS batch response
+#[derive(Deserialize, Debug)]
+struct LfsBatchResponse {
+    /// Objects of the request, with their actions
+    objects: Vec<LfsBatchObject>,
+}
+
+/// Object of a LFS batch response
+#[derive(Deserialize, Debug)]
+struct LfsBatchObject {
+    /// Sha256 of the content
+    oid: String,
+
+    /// Size of the content in bytes
+    size: u64,
+
+    /// Actions to do (none if the server already has the object for an upload)
+    #[serde(default)]
+    actions: LfsActions,
+
+    /// Error of the object
+    error: Option<LfsObjectError>,
+}
+
+/// Actions of an object of a LFS batch response
+#[derive(Deserialize, Default, Debug)]
+struct LfsActions {
+    /// Download of the object
+    download: Option<LfsAction>,
+
+    /// Upload of the object
+    upload: Option<LfsAction>,
+
+    /// Verification of the upload
+    verify: Option<LfsAction>,
+}
+
+/// Action of an object
+#[derive(Deserialize, Debug)]
+struct LfsAction {
+    /// Url of the action
+    href: String,
+
+    /// Headers to send to the url
+    #[serde(default)]
+    header: HashMap<String, String>,
+}
+
+/// Error of an object of a LFS batch response
+#[derive(Deserialize, Debug)]
+struct LfsObjectError {
+    /// Error message
+    message: String,
+}
+
+impl LfsEndpoint {
+    /// Request the batch API
+    async fn batch(
+        &self,
+        client: &reqwest::Client,
+        operation: &str,
+        objects: &[LfsObject],
+    ) -> Result<Vec<LfsBatchObject>, GitMoverError> {
+        let request = client
+            .post(format!("{}/objects/batch", self.url))
+            .basic_auth(&self.username, Some(&self.token))
+            .header(ACCEPT, LFS_MEDIA_TYPE)
+            .header(CONTENT_TYPE, LFS_MEDIA_TYPE)
+            .json(&LfsBatchRequest {
+                operation,
+                transfers: ["basic"],
+                objects,
+            });
+        let response: LfsBatchResponse = send(request).await?.json().await?;
+        for object in &response.objects {
+            if let Some(error) = &object.error {
+                return Err(GitMoverError::new(format!(
+                    "LFS {operation} of {} failed: {}",
+                    object.oid, error.message
+                )));
+            }
+        }
+        Ok(response.objects)
+    }
+}
+
+/// Build a request of an action, with its headers
+fn action_request(client: &reqwest::Client, method: Method, action: &LfsAction) -> RequestBuilder {
+    action.header.iter().fold(
+        client.request(method, &action.href),
+        |request, (name, value)| request.header(name, value),
+    )
+}
+
+/// Send a LFS request, failing on error statuses
+async fn send(request: RequestBuilder) -> Result<reqwest::Response, GitMoverError> {
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        let text = response.text().await?;
+        return Err(GitMoverError::new(format!("LFS request failed: {text}")));
+    }
+    Ok(response)
+}
+
+/// Parse a LFS pointer file
+fn parse_pointer(content: &[u8]) -> Option<LfsObject> {
+    let content = std::str::from_utf8(content).ok()?;
+    let mut lines = content.lines();
+    if lines.next()? != LFS_POINTER_VERSION {
+        return None;
+    }
+    let (mut oid, mut size) = (None, None);
+    for line in lines {
+        match line.split_once(' ') {
+            Some(("oid", value)) => oid = value.strip_prefix("sha256:"),
+            Some(("size", value)) => size = value.parse().ok(),
+            _ => {}
+        }
+    }
+    Some(LfsObject {
+        oid: oid?.to_string(),
+        size: size?,
+    })
+}
+
+/// Check if the tree of a commit has a `.gitattributes` with a LFS filter
+fn uses_lfs(repo: &git2::Repository, tree: &git2::Tree<'_>) -> bool {
+    tree.get_name(".gitattributes")
+        .and_then(|entry| repo.find_blob(entry.id()).ok())
+        .is_some_and(|blob| String::from_utf8_lossy(blob.content()).contains("filter=lfs"))
+}
+
+/// Find the LFS objects referenced in the history of the references,
+/// if the `.gitattributes` of one of them has a LFS filter
+/// # Errors
+/// Error if the repository can't be read
+pub(crate) fn find_lfs_objects(
+    repo: &git2::Repository,
+    ref_names: &[String],
+) -> Result<Vec<LfsObject>, GitMoverError> {
+    let mut tips = vec![];
+    for ref_name in ref_names {
+        if let Ok(commit) = repo.find_reference(ref_name)?.peel_to_commit() {
+            tips.push(commit);
+        }
+    }
+    if !tips
+        .iter()
+        .any(|commit| commit.tree().is_ok_and(|tree| uses_lfs(repo, &tree)))
+    {
+        return Ok(vec![]);
+    }
+    let mut revwalk = repo.revwalk()?;
+    for commit in &tips {
+        revwalk.push(commit.id())?;
+    }
+    let odb = repo.odb()?;
+    let mut seen = HashSet::new();
+    let mut objects = vec![];
+    for commit_id in revwalk {
+        let mut trees = vec![repo.find_commit(commit_id?)?.tree_id()];
+        while let Some(tree_id) = trees.pop() {
+            if !seen.insert(tree_id) {
+                continue;
+            }
+            for entry in repo.find_tree(tree_id)?.iter() {
+                match entry.kind() {
+                    Some(git2::ObjectType::Tree) => trees.push(entry.id()),
+                    Some(git2::ObjectType::Blob) if seen.insert(entry.id()) => {
+                        let (size, _) = odb.read_header(entry.id())?;
+                        if size > LFS_POINTER_MAX_SIZE {
+                            continue;
+                        }
+                        // the same pointer is the same blob, already seen
+                        if let Some(object) = parse_pointer(repo.find_blob(entry.id())?.content()) {
+                            objects.push(object);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(objects)
+}
+
+/// Migrate LFS objects, skipping the ones already on the destination.
+/// The objects are streamed from the source to the destination
+/// # Errors
+/// Error if a LFS request fails
+pub(crate) async fn sync_lfs_objects(
+    source: &LfsEndpoint,
+    destination: &LfsEndpoint,
+    objects: &[LfsObject],
+    loog: &(dyn Fn(&str) + Sync),
+) -> Result<usize, GitMoverError> {
+    let client = reqwest::Client::new();
+    let mut count = 0;
+    // the objects already on the destination count as done
+    let mut done = 0;
+    let progress = |done: usize| {
+        loog(&format!(
+            "Migrating LFS objects ({done}/{} done)...",
+            objects.len()
+        ));
+    };
+    for chunk in objects.chunks(LFS_BATCH_SIZE) {
+        let uploads = destination.batch(&client, "upload", chunk).await?;
+        let uploads: Vec<LfsBatchObject> = uploads
+            .into_iter()
+            .filter(|object| object.actions.upload.is_some())
+            .collect();
+        done += chunk.len() - uploads.len();
+        progress(done);
+        if uploads.is_empty() {
+            continue;
+        }
+        let missing: Vec<LfsObject> = uploads
+            .iter()
+            .map(|object| LfsObject {
+                oid: object.oid.clone(),
+                size: object.size,
+            })
+            .collect();
+        let downloads = source.batch(&client, "download", &missing).await?;
+        for upload in uploads {
+            let download = downloads
+                .iter()
+                .find(|download| download.oid == upload.oid)
+                .and_then(|download| download.actions.download.as_ref())
+                .ok_or_else(|| {
+                    GitMoverError::new(format!("LFS object {} not found on source", upload.oid))
+                })?;
+            let response = send(action_request(&client, Method::GET, download)).await?;
+            let content = reqwest::Body::wrap_stream(response.bytes_stream());
+            if let Some(action) = &upload.actions.upload {
+                let request = action_request(&client, Method::PUT, action)
+                    .header(CONTENT_TYPE, "application/octet-stream")
+                    .header(CONTENT_LENGTH, upload.size)
+                    .body(content);
+                send(request).await?;
+            }
+            if let Some(action) = &upload.actions.verify {
+                let request = action_request(&client, Method::POST, action)
+                    .header(ACCEPT, LFS_MEDIA_TYPE)
+                    .header(CONTENT_TYPE, LFS_MEDIA_TYPE)
+                    .json(&LfsObject {
+                        oid: upload.oid.clone(),
+                        size: upload.size,
+                    });
+                send(request).await?;
+            }
+            count += 1;
+            done += 1;
+            progress(done);
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        local::platform::LocalPlatform, platform::Platform, test_utils::local_repo_with_commit,
+        utils::Repo,
+    };
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, body_string, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const OID: &str = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+
+    #[test]
+    fn pointer() {
+        let pointer = format!("{LFS_POINTER_VERSION}\noid sha256:{OID}\nsize 12345\n");
+        assert_eq!(
+            parse_pointer(pointer.as_bytes()),
+            Some(LfsObject {
+                oid: OID.to_string(),
+                size: 12345
+            })
+        );
+        assert_eq!(parse_pointer(b"not a pointer"), None);
+    }
+
+    #[tokio::test]
+    async fn find_objects() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let platform = LocalPlatform::new(root.path().to_path_buf());
+        let lfs_repo = Repo {
+            name: "lfs".to_string(),
+            path: "lfs".to_string(),
+            ..Default::default()
+        };
+        let pointer = format!("{LFS_POINTER_VERSION}\noid sha256:{OID}\nsize 3\n");
+        local_repo_with_commit(
+            &platform,
+            &lfs_repo,
+            &[
+                (
+                    ".gitattributes",
+                    "*.bin filter=lfs diff=lfs merge=lfs -text\n",
+                ),
+                ("file.bin", &pointer),
+                ("README", "readme"),
+            ],
+        )
+        .await?;
+        let repo = git2::Repository::open_bare(platform.get_git_url("lfs"))?;
+
+        let objects = find_lfs_objects(&repo, &["refs/heads/main".to_string()])?;
+        assert_eq!(
+            objects,
+            vec![LfsObject {
+                oid: OID.to_string(),
+                size: 3
+            }]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sync_objects() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        Mock::given(method("POST"))
+            .and(path("/destination.git/info/lfs/objects/batch"))
+            .and(body_partial_json(json!({"operation": "upload"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"objects": [
+                {"oid": OID, "size": 3, "actions": {
+                    "upload": {"href": format!("{uri}/upload/{OID}"), "header": {"Authorization": "Basic upload"}},
+                    "verify": {"href": format!("{uri}/verify")}
+                }},
+                {"oid": "present", "size": 5}
+            ]})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/source.git/info/lfs/objects/batch"))
+            .and(body_partial_json(json!({"operation": "download", "objects": [{"oid": OID, "size": 3}]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"objects": [
+                {"oid": OID, "size": 3, "actions": {"download": {"href": format!("{uri}/download/{OID}")}}}
+            ]})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/download/{OID}")))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes("abc"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(format!("/upload/{OID}")))
+            .and(wiremock::matchers::header("authorization", "Basic upload"))
+            .and(body_string("abc"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/verify"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let endpoint = |name: &str| LfsEndpoint {
+            url: format!("{uri}/{name}.git/info/lfs"),
+            username: "user".to_string(),
+            token: "token".to_string(),
+        };
+        let objects = [
+            LfsObject {
+                oid: OID.to_string(),
+                size: 3,
+            },
+            LfsObject {
+                oid: "present".to_string(),
+                size: 5,
+            },
+        ];
+        let logs = std::sync::Mutex::new(Vec::new());
+        let count = sync_lfs_objects(
+            &endpoint("source"),
+            &endpoint("destination"),
+            &objects,
+            &|message| {
+                logs.lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .push(message.to_string())
+            },
+        )
+        .await?;
+        assert_eq!(count, 1);
+        // the object already present counts toward the progress
+        assert_eq!(
+            logs.into_inner()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .last()
+                .map(String::as_str),
+            Some("Migrating LFS objects (2/2 done)...")
+        );
+        Ok(())
+    }
+}
@@ -8,10 +8,21 @@
 //! Usage: git-mover [OPTIONS]
 //!
 //! Options:
-//!  -s, --source <SOURCE>            The source platform (github, gitlab, codeberg) [aliases: from]
-//!  -d, --destination <DESTINATION>  The destination platform (github, gitlab, codeberg) [aliases: to]
+//!  -s, --source <SOURCE>            The source platform (github, github-enterprise, gitlab, codeberg, gitea, bitbucket, bitbucket-server, azure, sourcehut, local, ssh) [aliases: from]
+//!  -d, --destination <DESTINATION>  The destination platform (github, github-enterprise, gitlab, codeberg, gitea, bitbucket, bitbucket-server, azure, sourcehut, local, ssh) [aliases: to]
 //!  -n, --no-forks                   Don't sync forked repositories
 //!  -r, --resync                     Resync all repositories
+//!      --yes                        Never prompt: answer yes to the questions and fail if a required value is missing (requires --private) [aliases: non-interactive]
+//!      --private <POLICY>           What to do with the private repositories (asked for each one by default) [possible values: sync, skip]
+//!      --delete <POLICY>            What to do with the repositories missing on the source (prompt by default, never in non-interactive mode) [possible values: never, prompt, always]
+//!      --issues                     Also migrate issues, comments and labels
+//!      --pull-requests              Also migrate pull requests (merge requests)
+//!      --releases                   Also migrate releases and their assets
+//!      --wiki                       Also migrate the wiki repository
+//!      --include-ref <GLOB>         Glob of the references to push, replacing the defaults (refs/heads/*, refs/tags/*, refs/notes/*)
+//!      --exclude-ref <GLOB>         Glob of the references to skip
+//!      --cache-dir <CACHE_DIR>      Directory keeping the mirrors between the runs, only fetching the changes
+//!      --dry-run                    Print what would be synced, edited and deleted, without changing anything
 //!  -c, --config <CONFIG>            Custom configuration file
 //!      --show-config-path           Show the current config path
 //!  -v, --verbose...                 Verbose mode (-v, -vv, -vvv)
@@ -29,21 +40,38 @@
     clippy::unwrap_used,
     clippy::expect_used
 )]
-#![warn(clippy::multiple_crate_versions)]
+// the duplicated versions come from transitive dependencies (getrandom, windows-sys...)
+// that can't be unified here: warning about them fails `cargo clippy -- -D warnings`
+#![allow(clippy::multiple_crate_versions)]
 
 pub(crate) mod cli;
 pub(crate) mod config;
+pub(crate) mod diff;
 pub(crate) mod errors;
+pub(crate) mod issue;
+pub(crate) mod lfs;
 pub(crate) mod macros;
 pub(crate) mod platform;
+pub(crate) mod pull_request;
+pub(crate) mod refs;
+pub(crate) mod release;
 pub(crate) mod sync;
+#[cfg(test)]
+pub(crate) mod test_utils;
 pub(crate) mod utils;
 pub(crate) use macros::config_password_wrap;
 pub(crate) use macros::config_value_wrap;
 
+mod azure;
+mod bitbucket;
+mod bitbucket_server;
 mod codeberg;
+mod gitea;
 mod github;
 mod gitlab;
+mod local;
+mod sourcehut;
+mod ssh;
 
 pub use cli::{git_mover_main, GitMoverCli};
 pub use config::GitMoverConfig;
@@ -0,0 +1,26 @@
+//! Local configuration
+use super::platform::LocalPlatform;
+use crate::{config::GitMoverConfig, config_value_wrap, errors::GitMoverError};
+use serde::{Deserialize, Serialize};
+
+/// Local configuration
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct LocalConfig {
+    /// Path of the directory containing the bare repositories
+    pub path: Option<String>,
+}
+
+impl LocalConfig {
+    /// Get the local platform
+    #[allow(clippy::needless_update)] // the config_value macro fills the other fields
+    pub fn get_plateform(config: &mut GitMoverConfig) -> Result<LocalPlatform, GitMoverError> {
+        let path = config_value_wrap!(
+            config,
+            local,
+            LocalConfig,
+            path,
+            "the path of the directory containing the bare repositories"
+        );
+        Ok(LocalPlatform::new(path.into()))
+    }
+}
@@ -0,0 +1,9 @@
+//! Local directory of bare repositories
+pub(crate) mod config;
+pub(crate) mod platform;
+
+/// Sidecar file marking a repository as private
+const PRIVATE_FILE: &str = "git-mover-private";
+
+/// Default description written by `git init`
+const DEFAULT_DESCRIPTION: &str = "Unnamed repository;";
@@ -0,0 +1,312 @@
+//! Local platform implementation
+use std::{
+    fs::{create_dir_all, read_dir, read_to_string, remove_dir_all, remove_file, write},
+    path::{Component, Path, PathBuf},
+    pin::Pin,
+};
+
+use super::{DEFAULT_DESCRIPTION, PRIVATE_FILE};
+use crate::{
+    errors::GitMoverError,
+    platform::{Platform, PlatformType},
+    utils::Repo,
+};
+
+/// Local platform: a directory of bare repositories (`<root>/<name>.git`)
+#[derive(Default, Debug, Clone)]
+pub struct LocalPlatform {
+    /// Root directory
+    root: PathBuf,
+}
+
+impl LocalPlatform {
+    /// Create a new local platform
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Get the path of a repository, refusing the names escaping the root (`..`, absolute paths)
+    fn repo_path(&self, name: &str) -> Result<PathBuf, GitMoverError> {
+        let is_inside_root = !name.is_empty()
+            && Path::new(name)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_inside_root {
+            return Err(GitMoverError::new(format!(
+                "Invalid repository name '{name}' for {}",
+                PlatformType::Local
+            )));
+        }
+        Ok(self.root.join(format!("{name}.git")))
+    }
+
+    /// Get the path of the wiki of a repository
+    fn wiki_path(&self, name: &str) -> Result<PathBuf, GitMoverError> {
+        self.repo_path(name)?;
+        Ok(self.root.join(format!("{name}.wiki.git")))
+    }
+
+    /// Read a repository from its directory
+    fn read_repo(&self, name: &str) -> Result<Repo, GitMoverError> {
+        let path = self.repo_path(name)?;
+        if !path.is_dir() {
+            return Err(GitMoverError::new(format!(
+                "Repository {} not found for {}",
+                path.display(),
+                PlatformType::Local
+            )));
+        }
+        let description = match read_to_string(path.join("description")) {
+            Ok(description) if !description.starts_with(DEFAULT_DESCRIPTION) => {
+                description.trim_end().to_string()
+            }
+            _ => String::new(),
+        };
+        Ok(Repo {
+            name: name.to_string(),
+            path: name.to_string(),
+            description,
+            private: path.join(PRIVATE_FILE).exists(),
+            fork: false,
+            ..Default::default()
+        })
+    }
+
+    /// Write the description and the privacy of a repository
+    fn write_repo(&self, repo: &Repo) -> Result<(), GitMoverError> {
+        let path = self.repo_path(&repo.path)?;
+        write(path.join("description"), format!("{}\n", repo.description))?;
+        let private_file = path.join(PRIVATE_FILE);
+        if repo.private {
+            write(private_file, "")?;
+        } else if private_file.exists() {
+            remove_file(private_file)?;
+        }
+        Ok(())
+    }
+}
+
+impl Platform for LocalPlatform {
+    fn check_git_access(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        Box::pin(async move {
+            create_dir_all(&self.root).map_err(|e| {
+                GitMoverError::new_with_source(
+                    format!("Cannot access to {}", self.root.display()),
+                    e,
+                )
+            })
+        })
+    }
+
+    fn get_remote_url(&self) -> String {
+        self.root.display().to_string()
+    }
+
+    fn get_git_url(&self, repo_name: &str) -> String {
+        // the names are checked before creating the repositories
+        self.root
+            .join(format!("{repo_name}.git"))
+            .display()
+            .to_string()
+    }
+
+    fn get_username(&self) -> &str {
+        ""
+    }
+
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Local
+    }
+
+    fn create_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        Box::pin(async move {
+            // the repository is pushed to its path
+            let path = self.repo_path(&repo.path)?;
+            if !path.exists() {
+                // same as `git init --bare <root>/<name>.git`
+                git2::Repository::init_bare(&path)?;
+            }
+            self.write_repo(&repo)
+        })
+    }
+
+    fn get_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Repo, GitMoverError>> + Send + '_>> {
+        let name = name.to_string();
+        Box::pin(async move { self.read_repo(&name) })
+    }
+
+    fn edit_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        Box::pin(async move {
+            self.read_repo(&repo.path)?;
+            self.write_repo(&repo)
+        })
+    }
+
+    fn get_all_repos(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Repo>, GitMoverError>> + Send>> {
+        let platform = self.clone();
+        Box::pin(async move {
+            let mut all_repos = vec![];
+            for entry in read_dir(&platform.root)? {
+                let entry = entry?;
+                if !entry.file_type()?.is_dir() {
+                    continue;
+                }
+                let file_name = entry.file_name();
+                let Some(name) = file_name
+                    .to_str()
+                    .and_then(|file_name| file_name.strip_suffix(".git"))
+                else {
+                    continue;
+                };
+                if name.ends_with(".wiki") {
+                    // wiki of another repository
+                    continue;
+                }
+                all_repos.push(platform.read_repo(name)?);
+            }
+            all_repos.sort_by(|a, b| a.name.cmp(&b.name));
+            println!("Requested local: {}", all_repos.len());
+            Ok(all_repos)
+        })
+    }
+
+    fn delete_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let name = name.to_string();
+        Box::pin(async move {
+            self.read_repo(&name)?;
+            remove_dir_all(self.repo_path(&name)?)?;
+            let wiki_path = self.wiki_path(&name)?;
+            if wiki_path.exists() {
+                remove_dir_all(wiki_path)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn set_default_branch(
+        &self,
+        repo_path: &str,
+        branch: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let path = self.repo_path(repo_path);
+        let head = format!("refs/heads/{branch}");
+        Box::pin(async move {
+            // same as `git symbolic-ref HEAD refs/heads/<branch>`
+            git2::Repository::open_bare(path?)?.set_head(&head)?;
+            Ok(())
+        })
+    }
+
+    fn has_wiki(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<bool, GitMoverError>> + Send + '_>> {
+        let wiki_path = self.wiki_path(repo_path);
+        Box::pin(async move { Ok(wiki_path?.is_dir()) })
+    }
+
+    fn enable_wiki(
+        &self,
+        repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let wiki_path = self.wiki_path(repo_path);
+        Box::pin(async move {
+            let wiki_path = wiki_path?;
+            if !wiki_path.exists() {
+                git2::Repository::init_bare(&wiki_path)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn local_repos() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let platform = LocalPlatform::new(root.path().to_path_buf());
+        platform.check_git_access().await?;
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            description: "my repo".to_string(),
+            private: true,
+            fork: false,
+            ..Default::default()
+        };
+        platform.create_repo(repo.clone()).await?;
+        assert_eq!(platform.get_all_repos().await?, vec![repo.clone()]);
+        let public_repo = Repo {
+            private: false,
+            ..repo
+        };
+        platform.edit_repo(public_repo.clone()).await?;
+        assert_eq!(platform.get_repo("repo").await?, public_repo);
+        platform.delete_repo("repo").await?;
+        assert!(platform.get_all_repos().await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_names() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let platform = LocalPlatform::new(root.path().join("root"));
+        platform.check_git_access().await?;
+        for name in ["../outside", "sub/../../outside", "/tmp/outside", "", "."] {
+            let repo = Repo {
+                name: name.to_string(),
+                path: name.to_string(),
+                ..Default::default()
+            };
+            assert!(platform.create_repo(repo).await.is_err(), "{name}");
+            assert!(platform.delete_repo(name).await.is_err(), "{name}");
+            assert!(platform.enable_wiki(name).await.is_err(), "{name}");
+        }
+        assert!(!root.path().join("outside.git").exists());
+        assert!(platform.get_all_repos().await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repo_path() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let platform = LocalPlatform::new(root.path().to_path_buf());
+        platform.check_git_access().await?;
+        let repo = Repo {
+            name: "My Repo".to_string(),
+            path: "my-repo".to_string(),
+            description: "my repo".to_string(),
+            ..Default::default()
+        };
+        platform.create_repo(repo.clone()).await?;
+        // the repository is created where it's pushed to
+        assert!(git2::Repository::open_bare(platform.get_git_url("my-repo")).is_ok());
+        platform
+            .edit_repo(Repo {
+                description: "edited".to_string(),
+                ..repo
+            })
+            .await?;
+        assert_eq!(platform.get_repo("my-repo").await?.description, "edited");
+        Ok(())
+    }
+}
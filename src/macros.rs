@@ -8,6 +8,18 @@ macro_rules! config_value {
                 $key_name: Some(value),
                 ..
             }) => value.clone(),
+            _ if $config.cli_args.yes => {
+                return Err(concat!(
+                    "Missing ",
+                    $string,
+                    " (`",
+                    stringify!($key_name),
+                    "` of `[",
+                    stringify!($setting_name),
+                    "]` in the config file)"
+                )
+                .into());
+            }
             _ => {
                 let _ = println!(concat!("Please enter ", $string, ":"));
                 let value = $func()?;
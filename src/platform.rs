@@ -2,6 +2,10 @@
 
 use crate::{
     errors::GitMoverError,
+    issue::{Comment, Issue, Label},
+    lfs::LfsEndpoint,
+    pull_request::PullRequest,
+    release::{Release, ReleaseAsset},
     utils::{check_ssh_access, Repo},
 };
 use serde::Deserialize;
@@ -13,7 +17,7 @@ pub trait Platform: Sync + Send {
     fn check_git_access(
         &self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
-        let url_ssh = format!("git@{}", self.get_remote_url());
+        let url_ssh = self.get_ssh_url();
         Box::pin(async move {
             let (stdout, stderr) = check_ssh_access(&url_ssh).await?;
             if stdout.contains(self.get_username()) || stderr.contains(self.get_username()) {
@@ -69,11 +73,259 @@ pub trait Platform: Sync + Send {
     /// Get the username of the platform.
     fn get_username(&self) -> &str;
 
+    /// Get the owner of the repositories (the username or an organization).
+    fn get_owner(&self) -> &str {
+        self.get_username()
+    }
+
     /// Get the platform type.
     fn get_remote_url(&self) -> String;
 
+    /// Get the ssh destination of the platform.
+    fn get_ssh_url(&self) -> String {
+        format!("git@{}", self.get_remote_url())
+    }
+
+    /// Get the git url of a repository on the platform.
+    fn get_git_url(&self, repo_name: &str) -> String {
+        format!(
+            "{}:{}/{}.git",
+            self.get_ssh_url(),
+            self.get_owner(),
+            repo_name
+        )
+    }
+
     /// get the type of the Platform
     fn get_type(&self) -> PlatformType;
+
+    /// Get the metadata fields reported for the repositories of the platform,
+    /// the other fields are ignored when comparing repositories
+    fn get_repo_fields(&self) -> &'static [RepoField] {
+        &[RepoField::Description, RepoField::Private]
+    }
+
+    /// Get the path a repository gets when created on the platform
+    /// (e.g. a lowercase slug), to match the repositories of the source
+    fn normalize_path(&self, path: &str) -> String {
+        path.to_string()
+    }
+
+    /// Get the labels of a repository.
+    fn get_labels(
+        &self,
+        _repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Label>, GitMoverError>> + Send + '_>>
+    {
+        not_supported("Issues", self.get_type())
+    }
+
+    /// Create a label in a repository.
+    fn create_label(
+        &self,
+        _repo_path: &str,
+        _label: Label,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        not_supported("Issues", self.get_type())
+    }
+
+    /// Get the issues (open and closed, without pull requests) of a repository.
+    fn get_issues(
+        &self,
+        _repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Issue>, GitMoverError>> + Send + '_>>
+    {
+        not_supported("Issues", self.get_type())
+    }
+
+    /// Create an issue (closed if needed) in a repository, returning its number.
+    fn create_issue(
+        &self,
+        _repo_path: &str,
+        _issue: Issue,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<u64, GitMoverError>> + Send + '_>> {
+        not_supported("Issues", self.get_type())
+    }
+
+    /// Get the comments of an issue.
+    fn get_issue_comments(
+        &self,
+        _repo_path: &str,
+        _number: u64,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Comment>, GitMoverError>> + Send + '_>>
+    {
+        not_supported("Issues", self.get_type())
+    }
+
+    /// Create a comment on an issue.
+    fn create_issue_comment(
+        &self,
+        _repo_path: &str,
+        _number: u64,
+        _comment: Comment,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        not_supported("Issues", self.get_type())
+    }
+
+    /// Get the pull requests (open, closed and merged) of a repository.
+    fn get_pull_requests(
+        &self,
+        _repo_path: &str,
+    ) -> Pin<
+        Box<dyn std::future::Future<Output = Result<Vec<PullRequest>, GitMoverError>> + Send + '_>,
+    > {
+        not_supported("Pull requests", self.get_type())
+    }
+
+    /// Create a pull request (closed if needed) in a repository, returning its number.
+    fn create_pull_request(
+        &self,
+        _repo_path: &str,
+        _pull_request: PullRequest,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<u64, GitMoverError>> + Send + '_>> {
+        not_supported("Pull requests", self.get_type())
+    }
+
+    /// Get the comments (including review comments) of a pull request.
+    fn get_pull_request_comments(
+        &self,
+        _repo_path: &str,
+        _number: u64,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Comment>, GitMoverError>> + Send + '_>>
+    {
+        not_supported("Pull requests", self.get_type())
+    }
+
+    /// Create a comment on a pull request.
+    fn create_pull_request_comment(
+        &self,
+        _repo_path: &str,
+        _number: u64,
+        _comment: Comment,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        not_supported("Pull requests", self.get_type())
+    }
+
+    /// Get the releases (with their assets) of a repository.
+    fn get_releases(
+        &self,
+        _repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Release>, GitMoverError>> + Send + '_>>
+    {
+        not_supported("Releases", self.get_type())
+    }
+
+    /// Create a release (without its assets) in a repository,
+    /// returning the reference used to upload its assets.
+    fn create_release(
+        &self,
+        _repo_path: &str,
+        _release: Release,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<String, GitMoverError>> + Send + '_>> {
+        not_supported("Releases", self.get_type())
+    }
+
+    /// Download an asset of a release, returning the response to stream.
+    fn download_release_asset(
+        &self,
+        _repo_path: &str,
+        _asset: ReleaseAsset,
+    ) -> Pin<
+        Box<dyn std::future::Future<Output = Result<reqwest::Response, GitMoverError>> + Send + '_>,
+    > {
+        not_supported("Releases", self.get_type())
+    }
+
+    /// Upload an asset to a release, from the reference returned by `create_release`.
+    fn upload_release_asset(
+        &self,
+        _repo_path: &str,
+        _release_reference: &str,
+        _asset: ReleaseAsset,
+        _content: reqwest::Body,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        not_supported("Releases", self.get_type())
+    }
+
+    /// Set the default branch of a repository (the branch must exist).
+    fn set_default_branch(
+        &self,
+        _repo_path: &str,
+        _branch: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        not_supported("Default branches", self.get_type())
+    }
+
+    /// Get the LFS server of a repository.
+    /// # Errors
+    /// Error if the platform doesn't support LFS
+    fn get_lfs_endpoint(&self, _repo_path: &str) -> Result<LfsEndpoint, GitMoverError> {
+        Err(GitMoverError::new_not_supported(format!(
+            "LFS objects are not supported for {}",
+            self.get_type()
+        )))
+    }
+
+    /// Get the git url of the wiki of a repository on the platform.
+    fn get_wiki_git_url(&self, repo_path: &str) -> String {
+        self.get_git_url(&format!("{repo_path}.wiki"))
+    }
+
+    /// Check if the wiki of a repository is enabled.
+    fn has_wiki(
+        &self,
+        _repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<bool, GitMoverError>> + Send + '_>> {
+        not_supported("Wikis", self.get_type())
+    }
+
+    /// Enable the wiki of a repository.
+    fn enable_wiki(
+        &self,
+        _repo_path: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        not_supported("Wikis", self.get_type())
+    }
+}
+
+/// Future failing because a feature isn't supported by a platform
+fn not_supported<T: Send + 'static>(
+    feature: &str,
+    platform_type: PlatformType,
+) -> Pin<Box<dyn std::future::Future<Output = Result<T, GitMoverError>> + Send>> {
+    let message = format!("{feature} are not supported for {platform_type}");
+    Box::pin(async move { Err(GitMoverError::new_not_supported(message)) })
+}
+
+/// Metadata field of a repository (the feature toggles are compared when known on both sides)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepoField {
+    /// Description of the repository
+    Description,
+
+    /// Whether the repository is private
+    Private,
+
+    /// Homepage of the repository
+    Homepage,
+
+    /// Whether the repository is archived
+    Archived,
+
+    /// Topics of the repository
+    Topics,
+}
+
+impl std::fmt::Display for RepoField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepoField::Description => write!(f, "description"),
+            RepoField::Private => write!(f, "private"),
+            RepoField::Homepage => write!(f, "homepage"),
+            RepoField::Archived => write!(f, "archived"),
+            RepoField::Topics => write!(f, "topics"),
+        }
+    }
 }
 
 /// The PlatformType enum is used to specify the platform type.
@@ -85,8 +337,32 @@ pub enum PlatformType {
     /// Github platform
     Github,
 
+    /// Github Enterprise Server platform
+    GithubEnterprise,
+
     /// Codeberg platform
     Codeberg,
+
+    /// Gitea (or Forgejo) platform
+    Gitea,
+
+    /// Bitbucket Cloud platform
+    Bitbucket,
+
+    /// Bitbucket Server (Data Center) platform
+    BitbucketServer,
+
+    /// Azure DevOps platform
+    Azure,
+
+    /// SourceHut platform
+    Sourcehut,
+
+    /// Local directory of bare repositories
+    Local,
+
+    /// Plain SSH git server
+    Ssh,
 }
 
 impl std::fmt::Display for PlatformType {
@@ -94,7 +370,15 @@ impl std::fmt::Display for PlatformType {
         match self {
             PlatformType::Gitlab => write!(f, "gitlab"),
             PlatformType::Github => write!(f, "github"),
+            PlatformType::GithubEnterprise => write!(f, "github-enterprise"),
             PlatformType::Codeberg => write!(f, "codeberg"),
+            PlatformType::Gitea => write!(f, "gitea"),
+            PlatformType::Bitbucket => write!(f, "bitbucket"),
+            PlatformType::BitbucketServer => write!(f, "bitbucket-server"),
+            PlatformType::Azure => write!(f, "azure"),
+            PlatformType::Sourcehut => write!(f, "sourcehut"),
+            PlatformType::Local => write!(f, "local"),
+            PlatformType::Ssh => write!(f, "ssh"),
         }
     }
 }
@@ -104,7 +388,15 @@ impl From<String> for PlatformType {
         match s.to_lowercase().as_str() {
             "gitlab" => PlatformType::Gitlab,
             "github" => PlatformType::Github,
+            "github-enterprise" | "github_enterprise" | "ghes" => PlatformType::GithubEnterprise,
             "codeberg" => PlatformType::Codeberg,
+            "gitea" | "forgejo" => PlatformType::Gitea,
+            "bitbucket" => PlatformType::Bitbucket,
+            "bitbucket-server" | "bitbucket_server" => PlatformType::BitbucketServer,
+            "azure" | "azure-devops" => PlatformType::Azure,
+            "sourcehut" | "srht" => PlatformType::Sourcehut,
+            "local" => PlatformType::Local,
+            "ssh" | "gitolite" => PlatformType::Ssh,
             _ => panic!("Invalid platform"),
         }
     }
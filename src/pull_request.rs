@@ -0,0 +1,471 @@
+//! Pull requests (merge requests) of a repository
+use std::fmt;
+
+use crate::{
+    errors::GitMoverError,
+    issue::{with_original_note, Comment, Issue},
+    platform::Platform,
+};
+
+/// State of a pull request
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum PullRequestState {
+    /// Open pull request
+    #[default]
+    Open,
+
+    /// Closed without being merged
+    Closed,
+
+    /// Merged pull request
+    Merged,
+}
+
+impl fmt::Display for PullRequestState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PullRequestState::Open => write!(f, "open"),
+            PullRequestState::Closed => write!(f, "closed"),
+            PullRequestState::Merged => write!(f, "merged"),
+        }
+    }
+}
+
+/// Pull request of a repository
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PullRequest {
+    /// Number of the pull request in the repository
+    pub number: u64,
+
+    /// Title of the pull request
+    pub title: String,
+
+    /// Body of the pull request
+    pub body: String,
+
+    /// Author of the pull request
+    pub author: String,
+
+    /// Creation date of the pull request
+    pub created_at: String,
+
+    /// Branch to merge
+    pub source_branch: String,
+
+    /// Branch to merge into
+    pub target_branch: String,
+
+    /// State of the pull request
+    pub state: PullRequestState,
+}
+
+/// Mapping of a migrated pull request
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequestMapping {
+    /// Number of the pull request on the source
+    pub old: u64,
+
+    /// Number of the pull request (or issue) on the destination
+    pub new: u64,
+
+    /// Whether the pull request was archived as an issue
+    pub as_issue: bool,
+}
+
+impl fmt::Display for PullRequestMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.as_issue {
+            write!(f, "#{} -> issue #{}", self.old, self.new)
+        } else {
+            write!(f, "#{} -> #{}", self.old, self.new)
+        }
+    }
+}
+
+/// Prefix a review comment with the file (and line) it's about
+pub(crate) fn with_file_reference(path: &str, line: Option<u64>, body: &str) -> String {
+    match line {
+        Some(line) => format!("On `{path}` line {line}:\n\n{body}"),
+        None => format!("On `{path}`:\n\n{body}"),
+    }
+}
+
+/// Get the title of the issue archiving a pull request
+fn archived_title(pull_request: &PullRequest) -> String {
+    format!("[PR #{}] {}", pull_request.number, pull_request.title)
+}
+
+/// Get the body of a migrated pull request, noting its origin
+fn migrated_body(pull_request: &PullRequest) -> String {
+    let origin = format!(
+        "Pull request #{} from `{}` into `{}` ({})",
+        pull_request.number,
+        pull_request.source_branch,
+        pull_request.target_branch,
+        pull_request.state
+    );
+    let body = if pull_request.body.is_empty() {
+        origin
+    } else {
+        format!("{origin}\n\n{}", pull_request.body)
+    };
+    with_original_note(
+        "opened",
+        &pull_request.author,
+        &pull_request.created_at,
+        &body,
+    )
+}
+
+/// Hidden marker of the source pull request in the body of a migrated pull request (or issue)
+fn source_marker(number: u64) -> String {
+    format!("<!-- git-mover: source pull request #{number} -->")
+}
+
+/// HTTP status of a pull request refused by the platform (e.g. no commits between the branches)
+const VALIDATION_FAILED: u16 = 422;
+
+/// Migrate the pull requests of a repository and their comments,
+/// as real pull requests when both branches exist on the destination,
+/// or as closed issues otherwise.
+/// Pull requests already migrated (same source number) are skipped
+/// # Errors
+/// Error if a platform doesn't support pull requests or if a request fails
+pub(crate) async fn sync_pull_requests(
+    source_platform: &dyn Platform,
+    destination_platform: &dyn Platform,
+    source_path: &str,
+    destination_path: &str,
+    branches: &[String],
+) -> Result<Vec<PullRequestMapping>, GitMoverError> {
+    // the pull requests archived as issues are marked too
+    let mut existing_bodies: Vec<String> = destination_platform
+        .get_pull_requests(destination_path)
+        .await?
+        .into_iter()
+        .map(|pull_request| pull_request.body)
+        .collect();
+    existing_bodies.extend(
+        destination_platform
+            .get_issues(destination_path)
+            .await?
+            .into_iter()
+            .map(|issue| issue.body),
+    );
+    let mut source_pull_requests = source_platform.get_pull_requests(source_path).await?;
+    source_pull_requests.sort_by_key(|pull_request| pull_request.number);
+    let mut mappings = vec![];
+    for pull_request in source_pull_requests {
+        let marker = source_marker(pull_request.number);
+        if existing_bodies.iter().any(|body| body.contains(&marker)) {
+            continue;
+        }
+        let comments = source_platform
+            .get_pull_request_comments(source_path, pull_request.number)
+            .await?;
+        let has_branches = branches.contains(&pull_request.source_branch)
+            && branches.contains(&pull_request.target_branch);
+        let new_pull_request = PullRequest {
+            body: format!("{}\n\n{marker}", migrated_body(&pull_request)),
+            ..pull_request.clone()
+        };
+        // fall back to an issue when the platform refuses the pull request as invalid
+        // (e.g. no commits between the branches once merged), other errors are reported
+        let created = match has_branches {
+            true => match destination_platform
+                .create_pull_request(destination_path, new_pull_request.clone())
+                .await
+            {
+                Ok(number) => Some(number),
+                Err(e) if e.status() == Some(VALIDATION_FAILED) => None,
+                Err(e) => return Err(e),
+            },
+            false => None,
+        };
+        let mapping = match created {
+            Some(number) => PullRequestMapping {
+                old: pull_request.number,
+                new: number,
+                as_issue: false,
+            },
+            None => {
+                let issue = Issue {
+                    number: pull_request.number,
+                    title: archived_title(&pull_request),
+                    body: new_pull_request.body,
+                    author: pull_request.author,
+                    created_at: pull_request.created_at,
+                    closed: true,
+                    labels: vec![],
+                };
+                let number = destination_platform
+                    .create_issue(destination_path, issue)
+                    .await?;
+                PullRequestMapping {
+                    old: pull_request.number,
+                    new: number,
+                    as_issue: true,
+                }
+            }
+        };
+        for comment in comments {
+            let new_comment = Comment {
+                body: with_original_note(
+                    "posted",
+                    &comment.author,
+                    &comment.created_at,
+                    &comment.body,
+                ),
+                ..comment
+            };
+            match mapping.as_issue {
+                true => {
+                    destination_platform
+                        .create_issue_comment(destination_path, mapping.new, new_comment)
+                        .await?
+                }
+                false => {
+                    destination_platform
+                        .create_pull_request_comment(destination_path, mapping.new, new_comment)
+                        .await?
+                }
+            }
+        }
+        mappings.push(mapping);
+    }
+    Ok(mappings)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        github::platform::GithubPlatform, gitlab::platform::GitlabPlatform, platform::PlatformType,
+    };
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Mount a paginated list, the second page being empty
+    async fn mount_list(server: &MockServer, list_path: &str, items: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn gitlab_to_github() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        mount_list(
+            &server,
+            "/api/v4/projects/user%2Frepo/merge_requests",
+            json!([
+                {"iid": 1, "title": "Old", "description": null, "author": {"username": "alice"}, "created_at": "2023-01-01", "state": "merged", "source_branch": "deleted", "target_branch": "main"},
+                {"iid": 2, "title": "WIP", "description": "Work", "author": {"username": "bob"}, "created_at": "2024-01-01", "state": "opened", "source_branch": "wip", "target_branch": "main"}
+            ]),
+        )
+        .await;
+        mount_list(
+            &server,
+            "/api/v4/projects/user%2Frepo/merge_requests/1/notes",
+            json!([]),
+        )
+        .await;
+        mount_list(
+            &server,
+            "/api/v4/projects/user%2Frepo/merge_requests/2/notes",
+            json!([
+                {"author": {"username": "carol"}, "created_at": "2024-01-02", "body": "Typo", "system": false, "position": {"new_path": "src/lib.rs", "new_line": 3}},
+                {"author": {"username": "bob"}, "created_at": "2024-01-03", "body": "added 1 commit", "system": true}
+            ]),
+        )
+        .await;
+        mount_list(&server, "/api/v3/repos/user/repo/pulls", json!([])).await;
+        mount_list(&server, "/api/v3/repos/user/repo/issues", json!([])).await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/repos/user/repo/pulls"))
+            .and(body_partial_json(json!({"title": "WIP", "head": "wip", "base": "main"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!(
+                {"number": 5, "title": "WIP", "body": null, "user": {"login": "user"}, "created_at": "2024-06-01", "state": "open", "merged_at": null, "head": {"ref": "wip"}, "base": {"ref": "main"}}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/repos/user/repo/issues/5/comments"))
+            .and(body_partial_json(json!({
+                "body": "_Originally posted by `carol` on 2024-01-02_\n\nOn `src/lib.rs` line 3:\n\nTypo"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/repos/user/repo/issues"))
+            .and(body_partial_json(json!({
+                "title": "[PR #1] Old",
+                "body": "_Originally opened by `alice` on 2023-01-01_\n\nPull request #1 from `deleted` into `main` (merged)\n\n<!-- git-mover: source pull request #1 -->"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!(
+                {"number": 6, "title": "[PR #1] Old", "body": null, "user": {"login": "user"}, "created_at": "2024-06-01", "state": "open", "labels": []}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/api/v3/repos/user/repo/issues/6"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let source =
+            GitlabPlatform::new("user".to_string(), "token".to_string(), Some(server.uri()));
+        let destination = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let branches = vec!["main".to_string(), "wip".to_string()];
+        let mappings = sync_pull_requests(&source, &destination, "repo", "repo", &branches).await?;
+        let mappings: Vec<String> = mappings.iter().map(|m| m.to_string()).collect();
+        assert_eq!(mappings, vec!["#1 -> issue #6", "#2 -> #5"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn already_migrated() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        mount_list(
+            &server,
+            "/api/v3/repos/user/source/pulls",
+            json!([
+                {"number": 1, "title": "Fix", "body": null, "user": {"login": "alice"}, "created_at": "2023-01-01", "state": "open", "merged_at": null, "head": {"ref": "fix"}, "base": {"ref": "main"}},
+                {"number": 2, "title": "Docs", "body": null, "user": {"login": "bob"}, "created_at": "2023-02-01", "state": "open", "merged_at": null, "head": {"ref": "docs"}, "base": {"ref": "main"}}
+            ]),
+        )
+        .await;
+        mount_list(
+            &server,
+            "/api/v3/repos/user/source/issues/2/comments",
+            json!([]),
+        )
+        .await;
+        mount_list(
+            &server,
+            "/api/v3/repos/user/source/pulls/2/comments",
+            json!([]),
+        )
+        .await;
+        // the first one was renamed after its migration, the title of the second one is taken
+        mount_list(
+            &server,
+            "/api/v3/repos/user/repo/pulls",
+            json!([
+                {"number": 7, "title": "Fix (renamed)", "body": "Migrated\n\n<!-- git-mover: source pull request #1 -->", "user": {"login": "user"}, "created_at": "2024-06-01", "state": "open", "merged_at": null, "head": {"ref": "fix"}, "base": {"ref": "main"}}
+            ]),
+        )
+        .await;
+        mount_list(
+            &server,
+            "/api/v3/repos/user/repo/issues",
+            json!([
+                {"number": 8, "title": "Docs", "body": "Unrelated", "user": {"login": "user"}, "created_at": "2024-06-01", "state": "open", "labels": []}
+            ]),
+        )
+        .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/repos/user/repo/pulls"))
+            .and(body_partial_json(json!({"title": "Docs", "head": "docs"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!(
+                {"number": 9, "title": "Docs", "body": null, "user": {"login": "user"}, "created_at": "2024-06-01", "state": "open", "merged_at": null, "head": {"ref": "docs"}, "base": {"ref": "main"}}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let platform = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let branches = vec!["main".to_string(), "fix".to_string(), "docs".to_string()];
+        let mappings =
+            sync_pull_requests(&platform, &platform, "source", "repo", &branches).await?;
+        let mappings: Vec<String> = mappings.iter().map(|m| m.to_string()).collect();
+        assert_eq!(mappings, vec!["#2 -> #9"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn refused_pull_request() -> Result<(), GitMoverError> {
+        for (status, as_issue) in [(422, true), (502, false)] {
+            let server = MockServer::start().await;
+            mount_list(
+                &server,
+                "/api/v3/repos/user/source/pulls",
+                json!([
+                    {"number": 1, "title": "Merged", "body": null, "user": {"login": "alice"}, "created_at": "2023-01-01", "state": "closed", "merged_at": "2023-01-02", "head": {"ref": "feature"}, "base": {"ref": "main"}}
+                ]),
+            )
+            .await;
+            mount_list(
+                &server,
+                "/api/v3/repos/user/source/issues/1/comments",
+                json!([]),
+            )
+            .await;
+            mount_list(
+                &server,
+                "/api/v3/repos/user/source/pulls/1/comments",
+                json!([]),
+            )
+            .await;
+            mount_list(&server, "/api/v3/repos/user/repo/pulls", json!([])).await;
+            mount_list(&server, "/api/v3/repos/user/repo/issues", json!([])).await;
+            Mock::given(method("POST"))
+                .and(path("/api/v3/repos/user/repo/pulls"))
+                .respond_with(ResponseTemplate::new(status).set_body_string("refused"))
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/api/v3/repos/user/repo/issues"))
+                .and(body_partial_json(json!({"title": "[PR #1] Merged"})))
+                .respond_with(ResponseTemplate::new(201).set_body_json(json!(
+                    {"number": 2, "title": "[PR #1] Merged", "body": null, "user": {"login": "user"}, "created_at": "2024-06-01", "state": "open", "labels": []}
+                )))
+                .expect(u64::from(as_issue))
+                .mount(&server)
+                .await;
+            Mock::given(method("PATCH"))
+                .and(path("/api/v3/repos/user/repo/issues/2"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+                .mount(&server)
+                .await;
+            let platform = GithubPlatform::new(
+                "user".to_string(),
+                "token".to_string(),
+                Some(server.uri()),
+                PlatformType::Github,
+            );
+            let branches = vec!["main".to_string(), "feature".to_string()];
+            let result =
+                sync_pull_requests(&platform, &platform, "source", "repo", &branches).await;
+            match as_issue {
+                true => assert_eq!(result?[0].to_string(), "#1 -> issue #2"),
+                false => assert_eq!(result.err().and_then(|e| e.status()), Some(502)),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+//! Policy of the references pushed to the destination
+use serde::{Deserialize, Serialize};
+
+/// References pushed by default: branches, tags and notes
+pub(crate) const DEFAULT_INCLUDE: [&str; 3] = ["refs/heads/*", "refs/tags/*", "refs/notes/*"];
+
+/// Globs of the references to push (`*` matches any characters, `?` matches one character),
+/// leaving out the platform-internal references (`refs/pull/*`, `refs/merge-requests/*`, ...)
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct RefPolicy {
+    /// References to push
+    #[serde(default = "default_include")]
+    pub include: Vec<String>,
+
+    /// References to skip, even if included
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// Get the default included references
+fn default_include() -> Vec<String> {
+    DEFAULT_INCLUDE
+        .iter()
+        .map(|glob| glob.to_string())
+        .collect()
+}
+
+impl Default for RefPolicy {
+    fn default() -> Self {
+        Self {
+            include: default_include(),
+            exclude: vec![],
+        }
+    }
+}
+
+impl RefPolicy {
+    /// Override the policy with the globs given on the command line:
+    /// the includes replace the configured ones, the excludes are added
+    pub fn with_globs(mut self, include: &[String], exclude: &[String]) -> Self {
+        if !include.is_empty() {
+            self.include = include.to_vec();
+        }
+        self.exclude.extend_from_slice(exclude);
+        self
+    }
+
+    /// Check if a reference should be pushed
+    pub fn allows(&self, ref_name: &str) -> bool {
+        self.include.iter().any(|glob| glob_match(glob, ref_name))
+            && !self.exclude.iter().any(|glob| glob_match(glob, ref_name))
+    }
+}
+
+/// Match a name against a glob (`*` matches any characters, including `/`)
+fn glob_match(glob: &str, name: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut g, mut n) = (0, 0);
+    // position of the last `*` in the glob and of the name when it was reached
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match glob.get(g) {
+            Some('*') => {
+                backtrack = Some((g, n));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                g += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, star_n)) => {
+                    // let the `*` match one more character
+                    g = star + 1;
+                    n = star_n + 1;
+                    backtrack = Some((star, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn globs() {
+        assert!(glob_match("refs/heads/*", "refs/heads/feature/x"));
+        assert!(glob_match("refs/*/v?", "refs/tags/v1"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("refs/heads/*", "refs/pull/1/head"));
+        assert!(!glob_match("refs/tags/v?", "refs/tags/v10"));
+    }
+
+    #[test]
+    fn default_policy() {
+        let policy = RefPolicy::default();
+        assert!(policy.allows("refs/heads/main"));
+        assert!(policy.allows("refs/tags/v1.0"));
+        assert!(policy.allows("refs/notes/commits"));
+        assert!(!policy.allows("refs/pull/1/head"));
+        assert!(!policy.allows("refs/merge-requests/1/head"));
+        assert!(!policy.allows("refs/keep-around/abc"));
+        assert!(!policy.allows("refs/remotes/origin/main"));
+        let policy = policy.with_globs(&[], &["refs/heads/tmp-*".to_string()]);
+        assert!(!policy.allows("refs/heads/tmp-1"));
+        assert!(policy.allows("refs/heads/main"));
+    }
+}
@@ -0,0 +1,298 @@
+//! Releases of a repository and their assets
+use crate::{errors::GitMoverError, platform::Platform};
+
+/// Asset (binary file) of a release
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReleaseAsset {
+    /// File name of the asset
+    pub name: String,
+
+    /// Size of the asset in bytes (0 if unknown)
+    pub size: u64,
+
+    /// Content type of the asset
+    pub content_type: String,
+
+    /// Url to download the asset
+    pub download_url: String,
+}
+
+/// Release of a repository
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Release {
+    /// Tag of the release
+    pub tag_name: String,
+
+    /// Name of the release
+    pub name: String,
+
+    /// Notes of the release
+    pub body: String,
+
+    /// Whether the release is a draft
+    pub draft: bool,
+
+    /// Whether the release is a prerelease
+    pub prerelease: bool,
+
+    /// Assets of the release
+    pub assets: Vec<ReleaseAsset>,
+
+    /// Reference of the release on its platform, to upload assets (empty when creating it)
+    pub reference: String,
+}
+
+/// Default content type of the assets
+pub(crate) const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Migrate the releases of a repository and their assets.
+/// The releases already existing (same tag) on the destination only get their missing assets (by name),
+/// completing a previous migration interrupted by a failed upload.
+/// Assets are streamed from the source to the destination
+/// # Errors
+/// Error if a platform doesn't support releases or if a request fails
+pub(crate) async fn sync_releases(
+    source_platform: &dyn Platform,
+    destination_platform: &dyn Platform,
+    source_path: &str,
+    destination_path: &str,
+) -> Result<usize, GitMoverError> {
+    let existing_releases = destination_platform.get_releases(destination_path).await?;
+    let mut source_releases = source_platform.get_releases(source_path).await?;
+    // releases are listed from the newest
+    source_releases.reverse();
+    let mut count = 0;
+    for release in source_releases {
+        let existing = existing_releases
+            .iter()
+            .find(|existing| existing.tag_name == release.tag_name);
+        let assets: Vec<ReleaseAsset> = match existing {
+            Some(existing) => release
+                .assets
+                .iter()
+                .filter(|asset| !existing.assets.iter().any(|a| a.name == asset.name))
+                .cloned()
+                .collect(),
+            None => release.assets.clone(),
+        };
+        let reference = match existing {
+            Some(_) if assets.is_empty() => continue,
+            Some(existing) => existing.reference.clone(),
+            None => {
+                destination_platform
+                    .create_release(destination_path, release)
+                    .await?
+            }
+        };
+        for asset in assets {
+            let response = source_platform
+                .download_release_asset(source_path, asset.clone())
+                .await?;
+            let asset = ReleaseAsset {
+                size: response.content_length().unwrap_or(asset.size),
+                ..asset
+            };
+            let content = reqwest::Body::wrap_stream(response.bytes_stream());
+            destination_platform
+                .upload_release_asset(destination_path, &reference, asset, content)
+                .await?;
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        gitea::platform::GiteaPlatform, github::platform::GithubPlatform, platform::PlatformType,
+    };
+    use serde_json::json;
+    use wiremock::matchers::{
+        body_partial_json, body_string_contains, header, method, path, query_param,
+        query_param_is_missing,
+    };
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn github_to_gitea() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        let asset_url = format!("{}/api/v3/repos/user/repo/releases/assets/1", server.uri());
+        Mock::given(method("GET"))
+            .and(path("/api/v3/repos/user/repo/releases"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"tag_name": "v2", "name": "v2", "body": null, "draft": false, "prerelease": false, "upload_url": "", "assets": []},
+                {"tag_name": "v1", "name": null, "body": "First", "draft": false, "prerelease": true, "upload_url": "", "assets": [
+                    {"name": "app.tar.gz", "size": 14, "content_type": "application/gzip", "url": asset_url}
+                ]}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/repos/user/repo/releases"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/repos/user/repo/releases/assets/1"))
+            .and(header("accept", "application/octet-stream"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes("binary-content"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/releases"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"id": 1, "tag_name": "v2", "name": "v2", "draft": false, "prerelease": false}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/releases"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/releases"))
+            .and(body_partial_json(json!({"tag_name": "v1", "name": "v1", "prerelease": true})))
+            .and(query_param_is_missing("name"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!(
+                {"id": 12, "tag_name": "v1", "name": "v1", "body": "First", "draft": false, "prerelease": true}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/releases/12/assets"))
+            .and(query_param("name", "app.tar.gz"))
+            .and(body_string_contains("binary-content"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let source = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let destination = GiteaPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            &server.uri(),
+            None,
+            None,
+            PlatformType::Gitea,
+        );
+        let count = sync_releases(&source, &destination, "repo", "repo").await?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failed_upload() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        let asset = |name: &str| json!({"name": name, "size": 4, "content_type": "text/plain", "url": format!("{}/api/v3/assets/{name}", server.uri())});
+        Mock::given(method("GET"))
+            .and(path("/api/v3/repos/user/repo/releases"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"tag_name": "v1", "name": "v1", "body": null, "draft": false, "prerelease": false, "upload_url": "", "assets": [asset("a.txt"), asset("b.txt")]}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/repos/user/repo/releases"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&server)
+            .await;
+        for name in ["a.txt", "b.txt"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v3/assets/{name}")))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(name))
+                .mount(&server)
+                .await;
+        }
+        // the first run creates the release, then fails to upload `b.txt`
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/releases"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/releases"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"id": 12, "tag_name": "v1", "name": "v1", "draft": false, "prerelease": false, "assets": [
+                    {"name": "a.txt", "size": 4, "browser_download_url": ""}
+                ]}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo/releases"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/releases"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!(
+                {"id": 12, "tag_name": "v1", "name": "v1", "draft": false, "prerelease": false}
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/releases/12/assets"))
+            .and(query_param("name", "a.txt"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/releases/12/assets"))
+            .and(query_param("name", "b.txt"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("storage error"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/user/repo/releases/12/assets"))
+            .and(query_param("name", "b.txt"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let source = GithubPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            Some(server.uri()),
+            PlatformType::Github,
+        );
+        let destination = GiteaPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            &server.uri(),
+            None,
+            None,
+            PlatformType::Gitea,
+        );
+        assert!(sync_releases(&source, &destination, "repo", "repo")
+            .await
+            .is_err());
+        // the next run only uploads the missing asset
+        let count = sync_releases(&source, &destination, "repo", "repo").await?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+//! SourceHut configuration
+use super::platform::SourcehutPlatform;
+use crate::{
+    config::GitMoverConfig, config_password_wrap, config_value_wrap, errors::GitMoverError,
+};
+use serde::{Deserialize, Serialize};
+
+/// SourceHut configuration
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct SourcehutConfig {
+    /// SourceHut username (without the `~`)
+    pub username: Option<String>,
+
+    /// SourceHut personal access token
+    pub token: Option<String>,
+}
+
+impl SourcehutConfig {
+    /// Get the sourcehut platform
+    pub fn get_plateform(config: &mut GitMoverConfig) -> Result<SourcehutPlatform, GitMoverError> {
+        let username = config_value_wrap!(
+            config,
+            sourcehut,
+            SourcehutConfig,
+            username,
+            "your sourcehut username"
+        );
+        let token = config_password_wrap!(
+            config,
+            sourcehut,
+            SourcehutConfig,
+            token,
+            "your sourcehut personal access token (https://meta.sr.ht/oauth2/personal-token)"
+        );
+        Ok(SourcehutPlatform::new(username, token))
+    }
+}
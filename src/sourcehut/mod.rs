@@ -0,0 +1,10 @@
+//! SourceHut (git.sr.ht) API module
+pub(crate) mod config;
+pub(crate) mod platform;
+pub(crate) mod repo;
+
+/// SourceHut git URL
+const SOURCEHUT_URL: &str = "git.sr.ht";
+
+/// SourceHut GraphQL API URL
+const SOURCEHUT_API_URL: &str = "https://git.sr.ht/query";
@@ -0,0 +1,322 @@
+//! SourceHut platform implementation
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::pin::Pin;
+
+use super::repo::{
+    SourcehutMe, SourcehutRepo, SourcehutRepositories, SourcehutRepository, SourcehutResponse,
+};
+use super::{SOURCEHUT_API_URL, SOURCEHUT_URL};
+
+use crate::errors::GitMoverError;
+use crate::platform::{Platform, PlatformType};
+use crate::utils::Repo;
+
+/// Fields requested for a repository
+const REPO_FIELDS: &str = "id name description visibility";
+
+/// SourceHut platform
+#[derive(Default, Debug, Clone)]
+pub struct SourcehutPlatform {
+    /// SourceHut username (without the `~`)
+    username: String,
+
+    /// SourceHut personal access token
+    token: String,
+
+    /// GraphQL API url
+    api_url: String,
+
+    /// Reqwest client
+    client: reqwest::Client,
+}
+
+impl SourcehutPlatform {
+    /// Create a new SourceHut platform
+    pub fn new(username: String, token: String) -> Self {
+        Self {
+            username: username.trim_start_matches('~').to_string(),
+            token,
+            api_url: SOURCEHUT_API_URL.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Run a GraphQL query
+    async fn query<T: DeserializeOwned>(
+        client: &reqwest::Client,
+        api_url: &str,
+        token: &str,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, GitMoverError> {
+        let response = client
+            .post(api_url)
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .header(ACCEPT, "application/json")
+            .header(CONTENT_TYPE, "application/json")
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let text = response.text().await?;
+            return Err(GitMoverError::new(format!(
+                "{text} for {}",
+                PlatformType::Sourcehut
+            )));
+        }
+        let text = response.text().await?;
+        let response: SourcehutResponse<T> = serde_json::from_str(&text)?;
+        match (response.data, response.errors) {
+            (_, Some(errors)) if !errors.is_empty() => {
+                let messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+                Err(GitMoverError::new(format!(
+                    "{} for {}",
+                    messages.join(", "),
+                    PlatformType::Sourcehut
+                )))
+            }
+            (Some(data), _) => Ok(data),
+            (None, _) => Err(GitMoverError::new(format!(
+                "{text} for {}",
+                PlatformType::Sourcehut
+            ))),
+        }
+    }
+
+    /// Get the SourceHut repository, with its id
+    async fn get_sourcehut_repo(&self, name: &str) -> Result<SourcehutRepo, GitMoverError> {
+        let query = format!(
+            "query ($name: String!) {{ me {{ repository(name: $name) {{ {REPO_FIELDS} }} }} }}"
+        );
+        let data: SourcehutMe<SourcehutRepository> = Self::query(
+            &self.client,
+            &self.api_url,
+            &self.token,
+            &query,
+            json!({ "name": name }),
+        )
+        .await?;
+        match data.me.repository {
+            Some(repo) => Ok(repo),
+            None => Err(GitMoverError::new(format!(
+                "Repository {name} not found for {}",
+                PlatformType::Sourcehut
+            ))),
+        }
+    }
+}
+
+/// Get the SourceHut visibility of a repository
+fn visibility(repo: &Repo) -> &'static str {
+    if repo.private {
+        "PRIVATE"
+    } else {
+        "PUBLIC"
+    }
+}
+
+impl Platform for SourcehutPlatform {
+    fn get_remote_url(&self) -> String {
+        SOURCEHUT_URL.to_string()
+    }
+
+    fn get_git_url(&self, repo_name: &str) -> String {
+        format!("{}:~{}/{}", self.get_ssh_url(), self.username, repo_name)
+    }
+
+    fn get_username(&self) -> &str {
+        &self.username
+    }
+
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Sourcehut
+    }
+
+    fn create_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        Box::pin(async move {
+            if let Ok(existing) = self.get_sourcehut_repo(&repo.name).await {
+                let existing: Repo = existing.into();
+                if existing.description != repo.description || existing.private != repo.private {
+                    return self.edit_repo(repo).await;
+                }
+                return Ok(());
+            }
+            let query = "mutation ($name: String!, $visibility: Visibility!, $description: String) \
+                { createRepository(name: $name, visibility: $visibility, description: $description) { id } }";
+            let _: Value = Self::query(
+                &self.client,
+                &self.api_url,
+                &self.token,
+                query,
+                json!({
+                    "name": repo.name,
+                    "visibility": visibility(&repo),
+                    "description": repo.description,
+                }),
+            )
+            .await?;
+            Ok(())
+        })
+    }
+
+    fn edit_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        Box::pin(async move {
+            let sourcehut_repo = self.get_sourcehut_repo(&repo.name).await?;
+            let query =
+                "mutation ($id: Int!, $input: RepoInput!) { updateRepository(id: $id, input: $input) { id } }";
+            let _: Value = Self::query(
+                &self.client,
+                &self.api_url,
+                &self.token,
+                query,
+                json!({
+                    "id": sourcehut_repo.id,
+                    "input": {
+                        "description": repo.description,
+                        "visibility": visibility(&repo),
+                    },
+                }),
+            )
+            .await?;
+            Ok(())
+        })
+    }
+
+    fn get_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Repo, GitMoverError>> + Send + '_>> {
+        let name = name.to_string();
+        Box::pin(async move { Ok(self.get_sourcehut_repo(&name).await?.into()) })
+    }
+
+    fn get_all_repos(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Repo>, GitMoverError>> + Send>> {
+        let token = self.token.clone();
+        let client = self.client.clone();
+        let api_url = self.api_url.clone();
+        Box::pin(async move {
+            let query = format!(
+                "query ($cursor: Cursor) {{ me {{ repositories(cursor: $cursor) {{ results {{ {REPO_FIELDS} }} cursor }} }} }}"
+            );
+            let mut cursor: Option<String> = None;
+            let mut page: usize = 1;
+            let mut all_repos = vec![];
+            loop {
+                let data: SourcehutMe<SourcehutRepositories> = Self::query(
+                    &client,
+                    &api_url,
+                    &token,
+                    &query,
+                    json!({ "cursor": cursor }),
+                )
+                .await?;
+                let repositories = data.me.repositories;
+                let repos: Vec<Repo> = repositories.results.into_iter().map(|r| r.into()).collect();
+                println!("Requested sourcehut (page {}): {}", page, repos.len());
+                all_repos.extend(repos);
+                match repositories.cursor {
+                    Some(next_cursor) => cursor = Some(next_cursor),
+                    None => break,
+                }
+                page += 1;
+            }
+            Ok(all_repos)
+        })
+    }
+
+    fn delete_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let name = name.to_string();
+        Box::pin(async move {
+            let sourcehut_repo = self.get_sourcehut_repo(&name).await?;
+            let query = "mutation ($id: Int!) { deleteRepository(id: $id) { id } }";
+            let _: Value = Self::query(
+                &self.client,
+                &self.api_url,
+                &self.token,
+                query,
+                json!({ "id": sourcehut_repo.id }),
+            )
+            .await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn sourcehut_git_url() {
+        let platform = SourcehutPlatform::new("~user".to_string(), "token".to_string());
+        assert_eq!(platform.get_git_url("repo"), "git@git.sr.ht:~user/repo");
+    }
+
+    #[tokio::test]
+    async fn sourcehut_pagination() -> Result<(), GitMoverError> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/query"))
+            .and(body_partial_json(json!({"variables": {"cursor": null}})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {"me": {"repositories": {
+                    "results": [{"id": 1, "name": "one", "description": null, "visibility": "PUBLIC"}],
+                    "cursor": "next"
+                }}}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/query"))
+            .and(body_partial_json(json!({"variables": {"cursor": "next"}})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {"me": {"repositories": {
+                    "results": [{"id": 2, "name": "two", "description": "desc", "visibility": "PRIVATE"}],
+                    "cursor": null
+                }}}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let mut platform = SourcehutPlatform::new("user".to_string(), "token".to_string());
+        platform.api_url = format!("{}/query", server.uri());
+        let repos = platform.get_all_repos().await?;
+        assert_eq!(repos.len(), 2);
+        assert!(!repos[0].private);
+        assert!(repos[1].private);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sourcehut_graphql_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": null,
+                "errors": [{"message": "Access denied"}]
+            })))
+            .mount(&server)
+            .await;
+        let mut platform = SourcehutPlatform::new("user".to_string(), "token".to_string());
+        platform.api_url = format!("{}/query", server.uri());
+        let result = platform.delete_repo("repo").await;
+        assert!(result.is_err_and(|e| e.to_string().contains("Access denied")));
+    }
+}
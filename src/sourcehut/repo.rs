@@ -0,0 +1,80 @@
+//! SourceHut Repo module
+use crate::utils::Repo;
+use serde::{Deserialize, Serialize};
+
+/// SourceHut Repo
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct SourcehutRepo {
+    /// Repo id
+    pub id: u64,
+
+    /// Repo name
+    pub name: String,
+
+    /// Repo description
+    pub description: Option<String>,
+
+    /// Repo visibility (`PUBLIC`, `UNLISTED` or `PRIVATE`)
+    pub visibility: String,
+}
+
+/// SourceHut page of repositories
+#[derive(Deserialize, Debug, Clone)]
+pub struct SourcehutRepoPage {
+    /// Repositories of the page
+    pub results: Vec<SourcehutRepo>,
+
+    /// Cursor of the next page
+    pub cursor: Option<String>,
+}
+
+/// SourceHut GraphQL response
+#[derive(Deserialize, Debug, Clone)]
+pub struct SourcehutResponse<T> {
+    /// Response data
+    pub data: Option<T>,
+
+    /// Response errors
+    pub errors: Option<Vec<SourcehutError>>,
+}
+
+/// SourceHut GraphQL error
+#[derive(Deserialize, Debug, Clone)]
+pub struct SourcehutError {
+    /// Error message
+    pub message: String,
+}
+
+/// SourceHut `me` query result
+#[derive(Deserialize, Debug, Clone)]
+pub struct SourcehutMe<T> {
+    /// Current user
+    pub me: T,
+}
+
+/// SourceHut repositories of the user
+#[derive(Deserialize, Debug, Clone)]
+pub struct SourcehutRepositories {
+    /// Page of repositories
+    pub repositories: SourcehutRepoPage,
+}
+
+/// SourceHut repository of the user
+#[derive(Deserialize, Debug, Clone)]
+pub struct SourcehutRepository {
+    /// Repository, if it exists
+    pub repository: Option<SourcehutRepo>,
+}
+
+impl From<SourcehutRepo> for Repo {
+    fn from(repo: SourcehutRepo) -> Self {
+        Repo {
+            name: repo.name.clone(),
+            path: repo.name,
+            description: repo.description.unwrap_or_default(),
+            private: repo.visibility == "PRIVATE",
+            fork: false, // no forks on SourceHut
+            ..Default::default()
+        }
+    }
+}
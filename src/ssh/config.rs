@@ -0,0 +1,76 @@
+//! SSH configuration
+use super::platform::{SshCommands, SshPlatform};
+use super::{
+    DEFAULT_CREATE_COMMAND, DEFAULT_DELETE_COMMAND, DEFAULT_DESCRIPTION_COMMAND,
+    DEFAULT_LIST_COMMAND, GITOLITE_CREATE_COMMAND, GITOLITE_DELETE_COMMAND, GITOLITE_LIST_COMMAND,
+};
+use crate::{config::GitMoverConfig, config_value_wrap, errors::GitMoverError};
+use serde::{Deserialize, Serialize};
+
+/// SSH configuration
+///
+/// Commands can use the `{name}` (path of the repository, e.g. `mirror/tool`), `{path}`
+/// (its directory on the host), `{root}` and `{description}` placeholders
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct SshConfig {
+    /// SSH destination (e.g. `git@mirror.example.com`)
+    pub host: Option<String>,
+
+    /// SSH port (default to 22)
+    pub ssh_port: Option<u16>,
+
+    /// Directory containing the repositories on the host (empty for gitolite, using its commands by default)
+    pub root: Option<String>,
+
+    /// Command creating a repository (default to `git init --bare {path}`, `create {name}` for gitolite)
+    pub create_command: Option<String>,
+
+    /// Command listing the repositories, printing their `.git` directories or the gitolite `info`
+    /// (default to `find {root} -mindepth 1 -maxdepth 1 -type d -name '*.git'`, `info` for gitolite)
+    pub list_command: Option<String>,
+
+    /// Command deleting a repository, one command per line
+    /// (default to `rm -rf {path}`, `D unlock {name}` then `D rm {name}` for gitolite)
+    pub delete_command: Option<String>,
+
+    /// Command setting the description of a repository (empty to disable, disabled for gitolite)
+    pub description_command: Option<String>,
+}
+
+impl SshConfig {
+    /// Get the ssh platform
+    pub fn get_plateform(config: &mut GitMoverConfig) -> Result<SshPlatform, GitMoverError> {
+        let host = config_value_wrap!(
+            config,
+            ssh,
+            SshConfig,
+            host,
+            "the ssh destination of your git server (e.g. git@mirror.example.com)"
+        );
+        let ssh_config = config.config_data.ssh.clone().unwrap_or_default();
+        let root = ssh_config.root.unwrap_or_default();
+        // without root, the host is a gitolite server
+        let defaults = match root.is_empty() {
+            true => [
+                GITOLITE_CREATE_COMMAND,
+                GITOLITE_LIST_COMMAND,
+                GITOLITE_DELETE_COMMAND,
+                "",
+            ],
+            false => [
+                DEFAULT_CREATE_COMMAND,
+                DEFAULT_LIST_COMMAND,
+                DEFAULT_DELETE_COMMAND,
+                DEFAULT_DESCRIPTION_COMMAND,
+            ],
+        };
+        let [create, list, delete, description] = defaults.map(|command| command.to_string());
+        let commands = SshCommands {
+            create: ssh_config.create_command.unwrap_or(create),
+            list: ssh_config.list_command.unwrap_or(list),
+            delete: ssh_config.delete_command.unwrap_or(delete),
+            description: ssh_config.description_command.unwrap_or(description),
+        };
+        Ok(SshPlatform::new(host, ssh_config.ssh_port, root, commands))
+    }
+}
@@ -0,0 +1,24 @@
+//! Plain SSH git server (gitolite or bare ssh host)
+pub(crate) mod config;
+pub(crate) mod platform;
+
+/// Default command used to create a repository
+const DEFAULT_CREATE_COMMAND: &str = "git init --bare {path}";
+
+/// Default command used to list the repositories (the `.git` directories of the root)
+const DEFAULT_LIST_COMMAND: &str = "find {root} -mindepth 1 -maxdepth 1 -type d -name '*.git'";
+
+/// Default command used to delete a repository
+const DEFAULT_DELETE_COMMAND: &str = "rm -rf {path}";
+
+/// Default command used to set the description of a repository
+const DEFAULT_DESCRIPTION_COMMAND: &str = "printf '%s\\n' {description} > {path}/description";
+
+/// Command used to create a repository on gitolite (a wild repo)
+const GITOLITE_CREATE_COMMAND: &str = "create {name}";
+
+/// Command used to list the repositories on gitolite
+const GITOLITE_LIST_COMMAND: &str = "info";
+
+/// Command used to delete a repository on gitolite (one command per line)
+const GITOLITE_DELETE_COMMAND: &str = "D unlock {name}\nD rm {name}";
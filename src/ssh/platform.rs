@@ -0,0 +1,417 @@
+//! SSH platform implementation
+use std::{
+    path::{Component, Path},
+    pin::Pin,
+    process::Stdio,
+};
+use tokio::process::Command;
+
+use crate::{
+    errors::GitMoverError,
+    platform::{Platform, PlatformType, RepoField},
+    utils::Repo,
+};
+
+/// Remote commands of the SSH platform
+#[derive(Default, Debug, Clone)]
+pub struct SshCommands {
+    /// Command creating a repository
+    pub create: String,
+
+    /// Command listing the repositories
+    pub list: String,
+
+    /// Command deleting a repository
+    pub delete: String,
+
+    /// Command setting the description of a repository
+    pub description: String,
+}
+
+/// SSH platform: a git server without HTTP API
+#[derive(Default, Debug, Clone)]
+pub struct SshPlatform {
+    /// SSH destination (e.g. `git@mirror.example.com`)
+    host: String,
+
+    /// SSH port
+    ssh_port: Option<u16>,
+
+    /// Directory containing the repositories on the host
+    root: String,
+
+    /// Remote commands
+    commands: SshCommands,
+}
+
+/// Quote a value for the remote shell, only when needed
+fn shell_quote(value: &str) -> String {
+    let is_safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./~@:+".contains(c));
+    if is_safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Parse the output of the list command (`.git` directories or gitolite `info`)
+fn parse_repo_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            let name = if line.starts_with(char::is_whitespace) {
+                // gitolite: " R W\trepo"
+                trimmed.split_whitespace().last()?
+            } else if trimmed.contains(char::is_whitespace) {
+                // gitolite banner, errors...
+                return None;
+            } else {
+                // `/srv/git/repo.git`, other files are not repositories
+                let path = trimmed.trim_end_matches('/');
+                let name = path.rsplit('/').next()?;
+                name.ends_with(".git").then_some(name)?
+            };
+            let name = name.trim_end_matches('/');
+            let name = name.strip_suffix(".git").unwrap_or(name);
+            if name.is_empty() || name.contains(['*', '@']) {
+                None
+            } else {
+                Some(name.to_string())
+            }
+        })
+        .collect()
+}
+
+impl SshPlatform {
+    /// Create a new SSH platform
+    pub fn new(host: String, ssh_port: Option<u16>, root: String, commands: SshCommands) -> Self {
+        Self {
+            host,
+            ssh_port,
+            root: root.trim_end_matches('/').to_string(),
+            commands,
+        }
+    }
+
+    /// Join the root of the host and the name of a repository
+    fn join_root(&self, name: &str) -> String {
+        if self.root.is_empty() {
+            format!("{name}.git")
+        } else {
+            format!("{}/{name}.git", self.root)
+        }
+    }
+
+    /// Get the path of a repository on the host, refusing the names escaping the root (`..`, absolute paths)
+    fn repo_path(&self, name: &str) -> Result<String, GitMoverError> {
+        let is_inside_root = !name.is_empty()
+            && Path::new(name)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_inside_root {
+            return Err(GitMoverError::new(format!(
+                "Invalid repository name '{name}' for {}",
+                PlatformType::Ssh
+            )));
+        }
+        Ok(self.join_root(name))
+    }
+
+    /// Fill the placeholders of a command, the repository being addressed by its path
+    /// # Errors
+    /// Error if the command is about a repository escaping the root
+    fn fill_command(&self, command: &str, repo: &Repo) -> Result<String, GitMoverError> {
+        let (name, path) = if command.contains("{name}") || command.contains("{path}") {
+            (
+                shell_quote(&repo.path),
+                shell_quote(&self.repo_path(&repo.path)?),
+            )
+        } else {
+            // e.g. the list command
+            (String::new(), String::new())
+        };
+        Ok(command
+            .replace("{name}", &name)
+            .replace("{path}", &path)
+            .replace("{root}", &shell_quote(&self.root))
+            .replace("{description}", &shell_quote(&repo.description)))
+    }
+
+    /// Run a command on the host
+    async fn run_command(&self, command: &str) -> Result<String, GitMoverError> {
+        let mut ssh = Command::new("ssh");
+        if let Some(port) = self.ssh_port {
+            ssh.arg("-p").arg(port.to_string());
+        }
+        let output = ssh
+            .arg("-o")
+            .arg("BatchMode=yes")
+            .arg(&self.host)
+            .arg(command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await?;
+        let stdout = str::from_utf8(&output.stdout)?.to_string();
+        if !output.status.success() {
+            let stderr = str::from_utf8(&output.stderr)?;
+            return Err(GitMoverError::new(format!(
+                "'{command}' failed on {}: {} for {}",
+                self.host,
+                stderr.trim(),
+                PlatformType::Ssh
+            )));
+        }
+        Ok(stdout)
+    }
+
+    /// Run a command of several lines on the host, one line after the other
+    async fn run_commands(&self, commands: &str) -> Result<(), GitMoverError> {
+        for command in commands.lines().filter(|line| !line.trim().is_empty()) {
+            self.run_command(command).await?;
+        }
+        Ok(())
+    }
+
+    /// List the repositories of the host
+    async fn list_repos(&self) -> Result<Vec<Repo>, GitMoverError> {
+        let command = self.fill_command(&self.commands.list, &Repo::default())?;
+        let output = self.run_command(&command).await?;
+        Ok(parse_repo_list(&output)
+            .into_iter()
+            .map(|name| Repo {
+                name: name.clone(),
+                path: name,
+                ..Default::default()
+            })
+            .collect())
+    }
+}
+
+impl Platform for SshPlatform {
+    fn check_git_access(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        Box::pin(async move {
+            self.list_repos().await?;
+            Ok(())
+        })
+    }
+
+    fn get_remote_url(&self) -> String {
+        match self.host.split_once('@') {
+            Some((_, host)) => host.to_string(),
+            None => self.host.clone(),
+        }
+    }
+
+    fn get_ssh_url(&self) -> String {
+        match self.ssh_port {
+            Some(port) => format!("ssh://{}:{}", self.host, port),
+            None => self.host.clone(),
+        }
+    }
+
+    fn get_git_url(&self, repo_name: &str) -> String {
+        // the names are checked by the commands creating the repositories
+        let path = self.join_root(repo_name);
+        match self.ssh_port {
+            Some(_) => format!("{}/{}", self.get_ssh_url(), path.trim_start_matches('/')),
+            None => format!("{}:{}", self.get_ssh_url(), path),
+        }
+    }
+
+    fn get_username(&self) -> &str {
+        match self.host.split_once('@') {
+            Some((user, _)) => user,
+            None => "",
+        }
+    }
+
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Ssh
+    }
+
+    fn get_repo_fields(&self) -> &'static [RepoField] {
+        // the server only lists the names of the repositories
+        &[]
+    }
+
+    fn create_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        Box::pin(async move {
+            let exists = self
+                .list_repos()
+                .await?
+                .iter()
+                .any(|existing| existing.path == repo.path);
+            if !exists {
+                let command = self.fill_command(&self.commands.create, &repo)?;
+                self.run_commands(&command).await?;
+            }
+            self.edit_repo(repo).await
+        })
+    }
+
+    fn get_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Repo, GitMoverError>> + Send + '_>> {
+        let name = name.to_string();
+        Box::pin(async move {
+            match self
+                .list_repos()
+                .await?
+                .into_iter()
+                .find(|repo| repo.path == name)
+            {
+                Some(repo) => Ok(repo),
+                None => Err(GitMoverError::new(format!(
+                    "Repository {name} not found for {}",
+                    PlatformType::Ssh
+                ))),
+            }
+        })
+    }
+
+    fn edit_repo(
+        &self,
+        repo: Repo,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        Box::pin(async move {
+            if self.commands.description.is_empty() || repo.description.is_empty() {
+                return Ok(());
+            }
+            let command = self.fill_command(&self.commands.description, &repo)?;
+            self.run_commands(&command).await
+        })
+    }
+
+    fn get_all_repos(
+        &self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<Repo>, GitMoverError>> + Send>> {
+        let platform = self.clone();
+        Box::pin(async move {
+            let repos = platform.list_repos().await?;
+            println!("Requested ssh: {}", repos.len());
+            Ok(repos)
+        })
+    }
+
+    fn delete_repo(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<(), GitMoverError>> + Send + '_>> {
+        let repo = Repo {
+            name: name.to_string(),
+            path: name.to_string(),
+            ..Default::default()
+        };
+        Box::pin(async move {
+            let command = self.fill_command(&self.commands.delete, &repo)?;
+            self.run_commands(&command).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ssh::{DEFAULT_CREATE_COMMAND, DEFAULT_DESCRIPTION_COMMAND};
+
+    #[test]
+    fn ssh_parse_repo_list() {
+        let ls = "repo1.git\nrepo2.git/\nREADME\n\n";
+        assert_eq!(parse_repo_list(ls), vec!["repo1", "repo2"]);
+        let find = "/srv/git/repo1.git\n/srv/git/repo2.git\n";
+        assert_eq!(parse_repo_list(find), vec!["repo1", "repo2"]);
+        let gitolite = "hello admin, this is git@mirror running gitolite3 v3.6.12 on git 2.39.2\n\n R W\tgitolite-admin\n R W\tmirror/tool\n R W C\tusers/CREATOR/..*\n";
+        assert_eq!(
+            parse_repo_list(gitolite),
+            vec!["gitolite-admin", "mirror/tool"]
+        );
+    }
+
+    #[test]
+    fn ssh_commands() -> Result<(), GitMoverError> {
+        let platform = SshPlatform::new(
+            "git@mirror.example.com".to_string(),
+            None,
+            "/srv/git/".to_string(),
+            SshCommands {
+                create: DEFAULT_CREATE_COMMAND.to_string(),
+                description: DEFAULT_DESCRIPTION_COMMAND.to_string(),
+                ..Default::default()
+            },
+        );
+        let repo = Repo {
+            name: "Repo".to_string(),
+            path: "repo".to_string(),
+            description: "it's a repo".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            platform.fill_command(&platform.commands.create, &repo)?,
+            "git init --bare /srv/git/repo.git"
+        );
+        assert_eq!(
+            platform.fill_command(&platform.commands.description, &repo)?,
+            "printf '%s\\n' 'it'\\''s a repo' > /srv/git/repo.git/description"
+        );
+        assert_eq!(
+            platform.get_git_url("repo"),
+            "git@mirror.example.com:/srv/git/repo.git"
+        );
+        assert_eq!(platform.get_remote_url(), "mirror.example.com");
+        assert_eq!(platform.get_username(), "git");
+        // `rm -rf {path}` must stay inside the root
+        for path in ["../outside", "sub/../../outside", "/srv", "", "."] {
+            let repo = Repo {
+                path: path.to_string(),
+                ..Default::default()
+            };
+            assert!(
+                platform.fill_command("rm -rf {path}", &repo).is_err(),
+                "{path}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn gitolite_defaults() -> Result<(), GitMoverError> {
+        let mut config = crate::config::GitMoverConfig::default();
+        config.config_data.ssh = Some(crate::ssh::config::SshConfig {
+            host: Some("git@mirror.example.com".to_string()),
+            ..Default::default()
+        });
+        let platform = crate::ssh::config::SshConfig::get_plateform(&mut config)?;
+        let repo = Repo {
+            name: "tool".to_string(),
+            path: "mirror/tool".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(platform.commands.list, "info");
+        assert_eq!(
+            platform.fill_command(&platform.commands.create, &repo)?,
+            "create mirror/tool"
+        );
+        assert_eq!(
+            platform.fill_command(&platform.commands.delete, &repo)?,
+            "D unlock mirror/tool\nD rm mirror/tool"
+        );
+        assert!(platform.commands.description.is_empty());
+        assert_eq!(
+            platform.get_git_url("mirror/tool"),
+            "git@mirror.example.com:mirror/tool.git"
+        );
+        Ok(())
+    }
+}
@@ -1,16 +1,76 @@
 //! Sync repositories from one platform to another
 use git2::Cred;
 use rand::{distr::Alphanumeric, rng, Rng};
-use std::{fs::remove_dir_all, path::PathBuf, sync::Arc};
+use std::{
+    fs::remove_dir_all,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::task::JoinSet;
 
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 
+use crate::cli::PrivatePolicy;
 use crate::errors::GitMoverError;
+use crate::issue::sync_issues;
+use crate::lfs::{find_lfs_objects, sync_lfs_objects, LfsObject};
 use crate::platform::Platform;
+use crate::pull_request::sync_pull_requests;
+use crate::refs::RefPolicy;
+use crate::release::sync_releases;
 use crate::utils::{yes_no_input, Repo};
 use crate::GitMoverConfig;
 
+/// Options of the sync of one repository
+#[derive(Default, Clone, Debug)]
+pub(crate) struct SyncOptions {
+    /// Migrate issues, comments and labels
+    pub issues: bool,
+
+    /// Migrate pull requests and their comments
+    pub pull_requests: bool,
+
+    /// Migrate releases and their assets
+    pub releases: bool,
+
+    /// Migrate the wiki repository
+    pub wiki: bool,
+
+    /// References to push
+    pub refs: RefPolicy,
+
+    /// Directory keeping the mirrors between the runs
+    pub cache_dir: Option<PathBuf>,
+
+    /// Policy of the private repositories, asked for each one if `None`
+    pub private: Option<PrivatePolicy>,
+
+    /// Fail instead of asking
+    pub non_interactive: bool,
+}
+
+impl From<&GitMoverConfig> for SyncOptions {
+    fn from(config: &GitMoverConfig) -> Self {
+        let cli_args = &config.cli_args;
+        let refs = config
+            .config_data
+            .refs
+            .clone()
+            .unwrap_or_default()
+            .with_globs(&cli_args.include_refs, &cli_args.exclude_refs);
+        Self {
+            issues: cli_args.issues,
+            pull_requests: cli_args.pull_requests,
+            releases: cli_args.releases,
+            wiki: cli_args.wiki,
+            refs,
+            cache_dir: cli_args.cache_dir.clone(),
+            private: cli_args.private,
+            non_interactive: cli_args.yes,
+        }
+    }
+}
+
 /// Sync repositories from one platform to another
 pub(crate) async fn sync_repos(
     config: &GitMoverConfig,
@@ -23,8 +83,18 @@ pub(crate) async fn sync_repos(
         .take(10)
         .map(char::from)
         .collect();
-    let temp_folder = std::env::temp_dir().join(format!("tmp-{rand_string}"));
-    std::fs::create_dir(&temp_folder)?;
+    let options = SyncOptions::from(config);
+    let temp_folder = match &options.cache_dir {
+        Some(cache_dir) => {
+            std::fs::create_dir_all(cache_dir)?;
+            cache_dir.clone()
+        }
+        None => {
+            let temp_folder = std::env::temp_dir().join(format!("tmp-{rand_string}"));
+            std::fs::create_dir(&temp_folder)?;
+            temp_folder
+        }
+    };
 
     let mut set = JoinSet::new();
     let verbose = config.cli_args.verbose;
@@ -40,12 +110,14 @@ pub(crate) async fn sync_repos(
         let source_ref = source_platform.clone();
         let destination_ref = destination_platform.clone();
         let temp_dir_ref = temp_folder.clone();
+        let options_ref = options.clone();
         let repo_name = one_repo.name.clone();
         let sync_repo = async move |repo_name, one_repo, pb| match sync_one_repo(
             source_ref,
             destination_ref,
             one_repo,
             temp_dir_ref,
+            options_ref,
             (verbose, &pb),
         )
         .await
@@ -84,31 +156,32 @@ pub(crate) async fn sync_repos(
     }
     let temp_folder_priv = temp_folder.clone();
     let progress = m.clone();
-    let sync_private = async move || match sync_private_repos(
-        source_platform,
-        destination_platform,
-        private_repos,
-        temp_folder_priv,
-        verbose,
-        progress,
-    )
-    .await
-    {
-        Ok(_) => {}
-        Err(e) => {
-            eprintln!("Error syncing private repos: {e}");
-        }
+    let sync_private = async move || {
+        sync_private_repos(
+            source_platform,
+            destination_platform,
+            private_repos,
+            temp_folder_priv,
+            options,
+            verbose,
+            progress,
+        )
+        .await
+        .map_err(|e| GitMoverError::from(format!("Error syncing private repos: {e}")))
     };
-    if config.cli_args.manual {
-        sync_private().await;
+    let private_result = if config.cli_args.manual {
+        sync_private().await
     } else {
-        set.spawn(async move { sync_private().await });
+        let private_task = tokio::spawn(async move { sync_private().await });
         set.join_all().await;
-    }
+        private_task.await.map_err(|e| e.to_string())?
+    };
 
-    println!("Cleaning up {}", temp_folder.display());
-    remove_dir_all(temp_folder)?;
-    Ok(())
+    if config.cli_args.cache_dir.is_none() {
+        println!("Cleaning up {}", temp_folder.display());
+        remove_dir_all(temp_folder)?;
+    }
+    private_result
 }
 
 /// Sync private repositories from one platform to another
@@ -117,16 +190,23 @@ async fn sync_private_repos(
     destination_platform: Arc<Box<dyn Platform>>,
     private_repos: Vec<Repo>,
     temp_folder: PathBuf,
+    options: SyncOptions,
     verbose: u8,
     progress: Arc<MultiProgress>,
 ) -> Result<(), GitMoverError> {
     let total = private_repos.len();
     for (idx, one_repo) in private_repos.into_iter().enumerate() {
-        let question = format!(
-            "Should sync private repo {} (y/n)",
-            one_repo.show_full_name()
-        );
-        match yes_no_input(&question)? {
+        let should_sync = match options.private {
+            Some(policy) => policy == PrivatePolicy::Sync,
+            None if options.non_interactive => {
+                return Err("Missing the policy of the private repos (--private)".into());
+            }
+            None => yes_no_input(format!(
+                "Should sync private repo {} (y/n)",
+                one_repo.show_full_name()
+            ))?,
+        };
+        match should_sync {
             true => {
                 let repo_name = one_repo.name.clone();
                 let source_ref = source_platform.clone();
@@ -141,6 +221,7 @@ async fn sync_private_repos(
                     destination_ref,
                     one_repo,
                     temp_folder.clone(),
+                    options.clone(),
                     (verbose, &pb),
                 )
                 .await
@@ -175,73 +256,400 @@ async fn sync_one_repo(
     destination_platform: Arc<Box<dyn Platform>>,
     repo: Repo,
     temp_folder: PathBuf,
+    options: SyncOptions,
     verbosity: (u8, &ProgressBar),
 ) -> Result<(), GitMoverError> {
-    let repo_cloned = repo.clone();
+    let destination_path = repo.flat_path();
+    // an archived repository is read-only and the default branch doesn't exist yet
+    let repo_cloned = Repo {
+        path: destination_path.clone(),
+        default_branch: String::new(),
+        archived: false,
+        ..repo.clone()
+    };
     let repo_name = repo.name.clone();
-    let (_verbose, pb) = verbosity;
+    let (verbose, pb) = verbosity;
     let loog = |log_line: &str| {
         pb.set_message(format!("{repo_name}: {log_line}"));
         pb.inc(1);
     };
     loog("Start syncing");
-    let tmp_repo_path = temp_folder.join(format!("{repo_name}.git"));
+    let cached = options.cache_dir.is_some();
 
     loog("Creating repo to destination...");
     destination_platform.create_repo(repo_cloned).await?;
     loog("Creating repo to destination done");
-    let source_platform = source_platform.as_ref();
+
+    let url = source_platform.get_git_url(&repo.path);
+    let next_remote = destination_platform.get_git_url(&destination_path);
+    let tmp_repo_path = temp_folder.join(mirror_name(&url));
+    let mirror = push_mirror(
+        &url,
+        &next_remote,
+        &tmp_repo_path,
+        &options.refs,
+        cached,
+        &loog,
+    )?;
+    if !mirror.skipped.is_empty() {
+        let skipped = if verbose > 0 {
+            format!(": {}", mirror.skipped.join(", "))
+        } else {
+            String::new()
+        };
+        pb.println(format!(
+            "{repo_name}: skipped {} references{skipped}",
+            mirror.skipped.len()
+        ));
+    }
+    for (ref_name, reason) in &mirror.rejected {
+        pb.println(format!("{repo_name}: '{ref_name}' rejected: {reason}"));
+    }
+    if let Some(default_branch) = &mirror.default_branch {
+        loog(&format!("Setting default branch to '{default_branch}'..."));
+        match destination_platform
+            .set_default_branch(&destination_path, default_branch)
+            .await
+        {
+            Ok(_) => loog(&format!(
+                "Setting default branch to '{default_branch}' done"
+            )),
+            Err(e) if e.is_not_supported() => {
+                pb.println(format!("{repo_name}: cannot set default branch: {e}"));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if !mirror.lfs_objects.is_empty() {
+        loog(&format!(
+            "Migrating LFS objects ({})...",
+            mirror.lfs_objects.len()
+        ));
+        let endpoints = source_platform
+            .get_lfs_endpoint(&repo.path)
+            .and_then(|source_lfs| {
+                Ok((
+                    source_lfs,
+                    destination_platform.get_lfs_endpoint(&destination_path)?,
+                ))
+            });
+        match endpoints {
+            Ok((source_lfs, destination_lfs)) => {
+                let count =
+                    sync_lfs_objects(&source_lfs, &destination_lfs, &mirror.lfs_objects, &loog)
+                        .await?;
+                loog(&format!("Migrating LFS objects done ({count} uploaded)"));
+            }
+            Err(e) if e.is_not_supported() => {
+                pb.println(format!("{repo_name}: cannot migrate LFS objects: {e}"));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    if options.wiki {
+        loog("Migrating wiki...");
+        let migrated = sync_wiki(
+            source_platform.as_ref().as_ref(),
+            destination_platform.as_ref().as_ref(),
+            &repo.path,
+            &destination_path,
+            &temp_folder,
+            cached,
+            (&loog, &|notice| {
+                pb.println(format!("{repo_name}: {notice}"))
+            }),
+        )
+        .await?;
+        if migrated {
+            loog("Migrating wiki done");
+        } else {
+            loog("No wiki to migrate");
+        }
+    }
+
+    if options.issues {
+        loog("Migrating issues...");
+        let count = sync_issues(
+            source_platform.as_ref().as_ref(),
+            destination_platform.as_ref().as_ref(),
+            &repo.path,
+            &destination_path,
+        )
+        .await?;
+        loog(&format!("Migrating issues done ({count} created)"));
+    }
+    if options.pull_requests {
+        loog("Migrating pull requests...");
+        let mappings = sync_pull_requests(
+            source_platform.as_ref().as_ref(),
+            destination_platform.as_ref().as_ref(),
+            &repo.path,
+            &destination_path,
+            &mirror.branches,
+        )
+        .await?;
+        let mappings: Vec<String> = mappings.iter().map(|m| m.to_string()).collect();
+        loog(&format!(
+            "Migrating pull requests done ({})",
+            mappings.join(", ")
+        ));
+    }
+    if options.releases {
+        loog("Migrating releases...");
+        let count = sync_releases(
+            source_platform.as_ref().as_ref(),
+            destination_platform.as_ref().as_ref(),
+            &repo.path,
+            &destination_path,
+        )
+        .await?;
+        loog(&format!("Migrating releases done ({count} created)"));
+    }
+    if repo.archived {
+        loog("Archiving repo...");
+        let archived_repo = Repo {
+            path: destination_path.clone(),
+            ..repo.clone()
+        };
+        destination_platform.edit_repo(archived_repo).await?;
+        loog("Archiving repo done");
+    }
+    Ok(())
+}
+
+/// Reason of a wiki missing on a platform although it is enabled
+const WIKI_FIRST_PAGE_HINT: &str =
+    "Github and Gitea only create the git repository of a wiki with its first page";
+
+/// Logger of a repository sync
+type Log<'a> = &'a (dyn Fn(&str) + Sync);
+
+/// Migrate the wiki of a repository, returning whether the source has one.
+/// A platform without wikis has none: `notice` tells it and the sync goes on
+/// # Errors
+/// Error if the wiki of the source can't be listed or the destination wiki can't be pushed to
+async fn sync_wiki(
+    source_platform: &dyn Platform,
+    destination_platform: &dyn Platform,
+    repo_path: &str,
+    destination_path: &str,
+    temp_folder: &Path,
+    cached: bool,
+    (loog, notice): (Log<'_>, Log<'_>),
+) -> Result<bool, GitMoverError> {
+    match source_platform.has_wiki(repo_path).await {
+        Ok(true) => {}
+        Ok(false) => return Ok(false),
+        Err(e) if e.is_not_supported() => {
+            notice(&format!("no wiki to migrate: {e}"));
+            return Ok(false);
+        }
+        Err(e) => return Err(e),
+    }
+    let wiki_url = source_platform.get_wiki_git_url(repo_path);
+    let refs = ls_remote(&wiki_url).map_err(|e| {
+        GitMoverError::new_with_source(
+            format!("Cannot list the wiki {wiki_url} ({WIKI_FIRST_PAGE_HINT})"),
+            e,
+        )
+    })?;
+    // a wiki without any page
+    if refs.is_empty() {
+        return Ok(false);
+    }
+    match destination_platform.enable_wiki(destination_path).await {
+        Ok(()) => {}
+        Err(e) if e.is_not_supported() => {
+            notice(&format!("cannot migrate the wiki: {e}"));
+            return Ok(false);
+        }
+        Err(e) => return Err(e),
+    }
+    let next_wiki_remote = destination_platform.get_wiki_git_url(destination_path);
+    let tmp_wiki_path = temp_folder.join(mirror_name(&wiki_url));
+    // the reference policy of the repositories doesn't apply to the wiki pages
+    push_mirror(
+        &wiki_url,
+        &next_wiki_remote,
+        &tmp_wiki_path,
+        &RefPolicy::default(),
+        cached,
+        loog,
+    )
+    .map_err(|e| {
+        GitMoverError::new_with_source(
+            format!(
+                "Cannot push the wiki to {next_wiki_remote} ({WIKI_FIRST_PAGE_HINT}: create one, then sync again)"
+            ),
+            e,
+        )
+    })?;
+    Ok(true)
+}
+
+/// Callbacks authenticating with the ssh agent
+fn ssh_callbacks<'a>() -> git2::RemoteCallbacks<'a> {
     let mut callbacks = git2::RemoteCallbacks::new();
     callbacks.credentials(move |_url, username_from_url, _allowed| {
         let username = username_from_url.unwrap_or("git");
         Cred::ssh_key_from_agent(username)
     });
+    callbacks
+}
 
+/// List the references of a remote repository, like `git ls-remote`
+pub(crate) fn ls_remote(url: &str) -> Result<Vec<(String, git2::Oid)>, GitMoverError> {
+    if Path::new(url).is_dir() {
+        // local repositories are read directly: listing an empty remote is not handled by git2
+        let repo = git2::Repository::open(url)?;
+        let mut refs = Vec::new();
+        if let Some(oid) = repo.head().ok().and_then(|head| head.target()) {
+            refs.push(("HEAD".to_string(), oid));
+        }
+        for reference in repo.references()? {
+            let reference = reference?;
+            let oid = reference.resolve().ok().and_then(|r| r.target());
+            if let (Some(name), Some(oid)) = (reference.name(), oid) {
+                refs.push((name.to_string(), oid));
+            }
+        }
+        return Ok(refs);
+    }
+    let mut remote = git2::Remote::create_detached(url)?;
+    remote.connect_auth(git2::Direction::Fetch, Some(ssh_callbacks()), None)?;
+    let refs = remote
+        .list()?
+        .iter()
+        .map(|head| (head.name().to_string(), head.oid()))
+        .collect();
+    remote.disconnect()?;
+    Ok(refs)
+}
+
+/// References pushed by [`push_mirror`]
+#[derive(Default, Clone, Debug)]
+pub(crate) struct Mirror {
+    /// Names of the pushed branches
+    pub branches: Vec<String>,
+
+    /// Default branch of the source (its HEAD), if any
+    pub default_branch: Option<String>,
+
+    /// References not pushed because of the [`RefPolicy`]
+    pub skipped: Vec<String>,
+
+    /// References rejected by the destination, with the reason
+    pub rejected: Vec<(String, String)>,
+
+    /// LFS objects referenced by the pushed references
+    pub lfs_objects: Vec<LfsObject>,
+}
+
+/// Maximum number of references pushed at once
+const PUSH_CHUNK_SIZE: usize = 500;
+
+/// Name of the mirror of a repository in the temporary (or cache) folder
+fn mirror_name(url: &str) -> String {
+    let name: String = url
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "._-".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.ends_with(".git") {
+        name
+    } else {
+        format!("{name}.git")
+    }
+}
+
+/// Clone a repository (bare, with all its references),
+/// or update the mirror kept from a previous run
+fn clone_mirror(
+    url: &str,
+    tmp_repo_path: &Path,
+    loog: &dyn Fn(&str),
+) -> Result<git2::Repository, GitMoverError> {
+    if tmp_repo_path.exists() {
+        loog(&format!(
+            "Fetching from '{}' to '{}'...",
+            url,
+            tmp_repo_path.display(),
+        ));
+        let repo = git2::Repository::open_bare(tmp_repo_path)?;
+        repo.remote_set_url("origin", url)?;
+        let mut remote = repo.find_remote("origin")?;
+        remote.connect_auth(git2::Direction::Fetch, Some(ssh_callbacks()), None)?;
+        let default_branch = remote.default_branch()?.as_str().map(|b| b.to_string());
+        remote.disconnect()?;
+        // same as `git fetch --prune`
+        let mut fetch_opts = git2::FetchOptions::new();
+        fetch_opts.remote_callbacks(ssh_callbacks());
+        fetch_opts.prune(git2::FetchPrune::On);
+        remote.fetch(&["+refs/*:refs/*"], Some(&mut fetch_opts), None)?;
+        drop(remote);
+        if let Some(default_branch) = default_branch {
+            repo.reference_symbolic(
+                "refs/remotes/origin/HEAD",
+                &default_branch,
+                true,
+                "default branch",
+            )?;
+        }
+        loog(&format!(
+            "Fetching from '{}' to '{}' done",
+            url,
+            tmp_repo_path.display(),
+        ));
+        return Ok(repo);
+    }
     let mut builder = git2::build::RepoBuilder::new();
     builder.bare(true);
+    // same as `git clone --mirror`
+    builder.remote_create(|repo, name, url| repo.remote_with_fetch(name, url, "+refs/*:refs/*"));
     let mut fetch_opts = git2::FetchOptions::new();
-    fetch_opts.remote_callbacks(callbacks);
+    fetch_opts.remote_callbacks(ssh_callbacks());
     builder.fetch_options(fetch_opts);
 
-    let url = format!(
-        "git@{}:{}/{}.git",
-        source_platform.get_remote_url(),
-        source_platform.get_username(),
-        &repo_name
-    );
-
     loog(&format!(
         "Cloning from '{}' to '{}'...",
         url,
         tmp_repo_path.display(),
     ));
-    let repo = builder.clone(&url, &tmp_repo_path)?;
+    let repo = builder.clone(url, tmp_repo_path)?;
     loog(&format!(
         "Cloning from '{}' to '{}' done",
         url,
         tmp_repo_path.display(),
     ));
-    let next_remote = format!(
-        "git@{}:{}/{}.git",
-        destination_platform.get_remote_url(),
-        destination_platform.get_username(),
-        &repo_name
-    );
-    let new_remote_name = "new_origin";
-    loog(&format!(
-        "Adding remote {} to {}",
-        new_remote_name, next_remote
-    ));
-    let mut remote = repo.remote(new_remote_name, &next_remote)?;
+    Ok(repo)
+}
+
+/// Clone a repository (bare, with all its references)
+/// and push the references allowed by the policy to another remote.
+/// The clone is kept if `cached`, to be fetched by the next run
+fn push_mirror(
+    url: &str,
+    next_remote: &str,
+    tmp_repo_path: &Path,
+    policy: &RefPolicy,
+    cached: bool,
+    loog: &dyn Fn(&str),
+) -> Result<Mirror, GitMoverError> {
+    let repo = clone_mirror(url, tmp_repo_path, loog)?;
+    // anonymous remote, not saved in the (cached) clone
+    let mut remote = repo.remote_anonymous(next_remote)?;
 
-    let mut callbacks = git2::RemoteCallbacks::new();
-    callbacks.credentials(move |_url, username_from_url, _allowed| {
-        let username = username_from_url.unwrap_or("git");
-        Cred::ssh_key_from_agent(username)
-    });
     loog(&format!("Connecting in push mode to {}", next_remote));
-    remote.connect_auth(git2::Direction::Push, Some(callbacks), None)?;
+    remote.connect_auth(git2::Direction::Push, Some(ssh_callbacks()), None)?;
 
+    let mut ref_names = vec![];
+    let mut skipped = vec![];
     let refs = repo.references()?;
     for reference in refs {
         let reference = reference?;
@@ -249,26 +657,74 @@ async fn sync_one_repo(
             Some(name) => name,
             None => continue,
         };
-        loog(&format!("Pushing '{ref_name}'..."));
-        let ref_remote = format!("+{ref_name}:{ref_name}");
-        let mut callbacks = git2::RemoteCallbacks::new();
-        callbacks.credentials(move |_url, username_from_url, _allowed| {
-            let username = username_from_url.unwrap_or("git");
-            Cred::ssh_key_from_agent(username)
+        if policy.allows(ref_name) {
+            ref_names.push(ref_name.to_string());
+        } else {
+            skipped.push(ref_name.to_string());
+        }
+    }
+
+    let mut rejected = vec![];
+    for (idx, chunk) in ref_names.chunks(PUSH_CHUNK_SIZE).enumerate() {
+        let pushed = idx * PUSH_CHUNK_SIZE + chunk.len();
+        loog(&format!(
+            "Pushing references {pushed}/{}...",
+            ref_names.len()
+        ));
+        let refspecs: Vec<String> = chunk
+            .iter()
+            .map(|ref_name| format!("+{ref_name}:{ref_name}"))
+            .collect();
+        let mut callbacks = ssh_callbacks();
+        // a rejected reference doesn't abort the push of the others
+        callbacks.push_update_reference(|ref_name, status| {
+            if let Some(status) = status {
+                rejected.push((ref_name.to_string(), status.to_string()));
+            }
+            Ok(())
         });
         let mut opts = git2::PushOptions::new();
         opts.remote_callbacks(callbacks);
-        remote.push(&[&ref_remote], Some(&mut opts))?;
-        loog(&format!("Pushing '{ref_name}' done"));
+        remote.push(&refspecs, Some(&mut opts))?;
     }
-    remove_dir_all(tmp_repo_path)?;
-    Ok(())
+    loog(&format!("Pushing {} references done", ref_names.len()));
+
+    let pushed: Vec<String> = ref_names
+        .into_iter()
+        .filter(|ref_name| !rejected.iter().any(|(rejected, _)| rejected == ref_name))
+        .collect();
+    let branches: Vec<String> = pushed
+        .iter()
+        .filter_map(|ref_name| ref_name.strip_prefix("refs/heads/"))
+        .map(|branch| branch.to_string())
+        .collect();
+    loog("Looking for LFS objects...");
+    let lfs_objects = find_lfs_objects(&repo, &pushed)?;
+    // the mirror clone records the default branch of the source in `refs/remotes/origin/HEAD`
+    let default_branch = ["refs/remotes/origin/HEAD", "HEAD"]
+        .iter()
+        .find_map(|name| {
+            let reference = repo.find_reference(name).ok()?;
+            let target = reference.symbolic_target()?.strip_prefix("refs/heads/")?;
+            branches.iter().find(|pushed| *pushed == target).cloned()
+        });
+    if !cached {
+        remove_dir_all(tmp_repo_path)?;
+    }
+    Ok(Mirror {
+        branches,
+        default_branch,
+        skipped,
+        rejected,
+        lfs_objects,
+    })
 }
 
-/// Delete repositories from a platform
+/// Delete repositories from a platform, asking for each one if `ask` is set
 pub(crate) async fn delete_repos(
     destination_platform: Arc<Box<dyn Platform>>,
     repos: Vec<Repo>,
+    ask: bool,
 ) -> Result<(), GitMoverError> {
     for (idx, one_repo) in repos.iter().enumerate() {
         let question = format!(
@@ -277,7 +733,7 @@ pub(crate) async fn delete_repos(
             idx,
             repos.len()
         );
-        let should_delete = yes_no_input(&question)?;
+        let should_delete = !ask || yes_no_input(&question)?;
         if should_delete {
             match destination_platform.delete_repo(&one_repo.path).await {
                 Ok(_) => {
@@ -298,6 +754,399 @@ pub(crate) async fn delete_repos(
 mod test {
 
     use super::*;
+    use crate::local::platform::LocalPlatform;
+    use crate::test_utils::{commit_files, local_repo_with_commit};
+
+    #[tokio::test]
+    async fn test_sync_local() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let source = LocalPlatform::new(root.path().join("source"));
+        let destination = LocalPlatform::new(root.path().join("destination"));
+        source.check_git_access().await?;
+        destination.check_git_access().await?;
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            description: "synced repo".to_string(),
+            private: false,
+            fork: false,
+            ..Default::default()
+        };
+        let commit = local_repo_with_commit(&source, &repo, &[]).await?;
+        let source_repo = git2::Repository::open_bare(source.get_git_url("repo"))?;
+        source_repo.reference("refs/tags/v1", commit, false, "tag")?;
+        source_repo.reference("refs/pull/1/head", commit, false, "pull request")?;
+
+        let temp_folder = root.path().join("tmp");
+        std::fs::create_dir_all(&temp_folder)?;
+        let pb = ProgressBar::hidden();
+        sync_one_repo(
+            Arc::new(Box::new(source)),
+            Arc::new(Box::new(destination.clone())),
+            repo.clone(),
+            temp_folder,
+            SyncOptions::default(),
+            (0, &pb),
+        )
+        .await?;
+
+        assert_eq!(destination.get_all_repos().await?, vec![repo]);
+        let destination_repo = git2::Repository::open_bare(destination.get_git_url("repo"))?;
+        let pushed = destination_repo.find_reference("refs/heads/main")?;
+        assert_eq!(pushed.target(), Some(commit));
+        let head = destination_repo.find_reference("HEAD")?;
+        assert_eq!(head.symbolic_target(), Some("refs/heads/main"));
+        assert!(destination_repo.find_reference("refs/tags/v1").is_ok());
+        assert!(destination_repo.find_reference("refs/pull/1/head").is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_local_wiki() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let source = LocalPlatform::new(root.path().join("source"));
+        let destination = LocalPlatform::new(root.path().join("destination"));
+        source.check_git_access().await?;
+        destination.check_git_access().await?;
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            description: String::new(),
+            private: false,
+            fork: false,
+            ..Default::default()
+        };
+        local_repo_with_commit(&source, &repo, &[]).await?;
+        source.enable_wiki("repo").await?;
+        let wiki_commit = commit_files(&source.get_wiki_git_url("repo"), &[], "Home")?;
+        assert_eq!(
+            ls_remote(&source.get_wiki_git_url("repo"))?,
+            vec![
+                ("HEAD".to_string(), wiki_commit),
+                ("refs/heads/main".to_string(), wiki_commit)
+            ]
+        );
+
+        let temp_folder = root.path().join("tmp");
+        std::fs::create_dir_all(&temp_folder)?;
+        let pb = ProgressBar::hidden();
+        // the reference policy of the repository leaves out the branches, not the wiki pages
+        let options = SyncOptions {
+            wiki: true,
+            refs: RefPolicy {
+                include: vec!["refs/tags/*".to_string()],
+                exclude: vec![],
+            },
+            ..Default::default()
+        };
+        sync_one_repo(
+            Arc::new(Box::new(source)),
+            Arc::new(Box::new(destination.clone())),
+            repo.clone(),
+            temp_folder,
+            options,
+            (0, &pb),
+        )
+        .await?;
+
+        assert!(destination.has_wiki("repo").await?);
+        assert_eq!(destination.get_all_repos().await?, vec![repo]);
+        let wiki = git2::Repository::open_bare(destination.get_wiki_git_url("repo"))?;
+        let pushed = wiki.find_reference("refs/heads/main")?;
+        assert_eq!(pushed.target(), Some(wiki_commit));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_remote_wiki() -> Result<(), GitMoverError> {
+        use crate::gitea::platform::GiteaPlatform;
+        use crate::platform::PlatformType;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let root = tempfile::tempdir()?;
+        let local = LocalPlatform::new(root.path().join("local"));
+        local.check_git_access().await?;
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            ..Default::default()
+        };
+        local.create_repo(repo.clone()).await?;
+        local.enable_wiki("repo").await?;
+        commit_files(&local.get_wiki_git_url("repo"), &[], "Home")?;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/user/repo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!(
+                {"name": "repo", "description": "", "private": false, "fork": false, "has_wiki": true}
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/api/v1/repos/user/repo"))
+            .and(body_partial_json(serde_json::json!({"has_wiki": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // nothing listens on this port: the wiki repository can't be reached
+        let closed_port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+        let gitea = GiteaPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            &server.uri(),
+            None,
+            Some(closed_port),
+            PlatformType::Gitea,
+        );
+        let temp_folder = root.path().join("tmp");
+        std::fs::create_dir_all(&temp_folder)?;
+        // the destination wiki doesn't exist until its first page is created
+        let error = sync_wiki(
+            &local,
+            &gitea,
+            "repo",
+            "repo",
+            &temp_folder,
+            false,
+            (&|_| {}, &|_| {}),
+        )
+        .await
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+        assert!(error.starts_with("Cannot push the wiki to ssh://git@127.0.0.1"));
+        assert!(error.contains("first page"));
+
+        // the source wiki can't be listed: the error is not mistaken for a missing wiki
+        let error = sync_wiki(
+            &gitea,
+            &local,
+            "repo",
+            "repo",
+            &temp_folder,
+            false,
+            (&|_| {}, &|_| {}),
+        )
+        .await
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+        assert!(error.starts_with("Cannot list the wiki ssh://git@127.0.0.1"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_wiki_not_supported() -> Result<(), GitMoverError> {
+        use crate::bitbucket_server::platform::BitbucketServerPlatform;
+
+        let root = tempfile::tempdir()?;
+        let local = LocalPlatform::new(root.path().join("local"));
+        local.check_git_access().await?;
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            ..Default::default()
+        };
+        local.create_repo(repo.clone()).await?;
+        local.enable_wiki("repo").await?;
+        commit_files(&local.get_wiki_git_url("repo"), &[], "Home")?;
+        // Bitbucket Server has no wikis: no request is sent
+        let bitbucket = BitbucketServerPlatform::new(
+            "user".to_string(),
+            "token".to_string(),
+            "http://127.0.0.1",
+            "PROJ".to_string(),
+            None,
+        );
+        let temp_folder = root.path().join("tmp");
+        std::fs::create_dir_all(&temp_folder)?;
+
+        for (source, destination) in [
+            (&local as &dyn Platform, &bitbucket as &dyn Platform),
+            (&bitbucket, &local),
+        ] {
+            let notices = std::sync::Mutex::new(Vec::new());
+            let migrated = sync_wiki(
+                source,
+                destination,
+                "repo",
+                "repo",
+                &temp_folder,
+                false,
+                (&|_| {}, &|notice| {
+                    notices
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .push(notice.to_string());
+                }),
+            )
+            .await?;
+            assert!(!migrated);
+            assert_eq!(
+                notices
+                    .into_inner()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .len(),
+                1
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_push_mirror_rejected() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let source = LocalPlatform::new(root.path().join("source"));
+        let destination = LocalPlatform::new(root.path().join("destination"));
+        source.check_git_access().await?;
+        destination.check_git_access().await?;
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            ..Default::default()
+        };
+        let commit = local_repo_with_commit(&source, &repo, &[]).await?;
+        destination.create_repo(repo).await?;
+        let source_repo = git2::Repository::open_bare(source.get_git_url("repo"))?;
+        source_repo.reference("refs/heads/locked", commit, false, "branch")?;
+        source_repo.reference("refs/tags/v1", commit, false, "tag")?;
+        // the destination can't update a locked reference
+        let destination_url = destination.get_git_url("repo");
+        std::fs::write(
+            Path::new(&destination_url).join("refs/heads/locked.lock"),
+            "",
+        )?;
+
+        let mirror = push_mirror(
+            &source.get_git_url("repo"),
+            &destination_url,
+            &root.path().join("tmp").join("repo"),
+            &RefPolicy::default(),
+            false,
+            &|_| {},
+        )?;
+        assert_eq!(mirror.rejected.len(), 1);
+        assert_eq!(mirror.rejected[0].0, "refs/heads/locked");
+        assert_eq!(mirror.branches, vec!["main".to_string()]);
+        let destination_repo = git2::Repository::open_bare(&destination_url)?;
+        assert_eq!(
+            destination_repo.find_reference("refs/heads/main")?.target(),
+            Some(commit)
+        );
+        assert!(destination_repo.find_reference("refs/tags/v1").is_ok());
+        assert!(destination_repo
+            .find_reference("refs/heads/locked")
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_default_branch_error() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let source = LocalPlatform::new(root.path().join("source"));
+        let destination = LocalPlatform::new(root.path().join("destination"));
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            ..Default::default()
+        };
+        local_repo_with_commit(&source, &repo, &[]).await?;
+        destination.create_repo(repo.clone()).await?;
+        // the default branch of the destination can't be changed
+        std::fs::write(
+            Path::new(&destination.get_git_url("repo")).join("HEAD.lock"),
+            "",
+        )?;
+
+        let temp_folder = root.path().join("tmp");
+        std::fs::create_dir_all(&temp_folder)?;
+        let pb = ProgressBar::hidden();
+        let result = sync_one_repo(
+            Arc::new(Box::new(source)),
+            Arc::new(Box::new(destination.clone())),
+            repo,
+            temp_folder,
+            SyncOptions::default(),
+            (0, &pb),
+        )
+        .await;
+        assert!(result.is_err_and(|e| !e.is_not_supported()));
+        let destination_repo = git2::Repository::open_bare(destination.get_git_url("repo"))?;
+        assert!(destination_repo.find_reference("refs/heads/main").is_ok());
+
+        // a platform without default branches is not an error of the sync
+        let ssh = crate::ssh::platform::SshPlatform::default();
+        let unsupported = ssh.set_default_branch("repo", "main").await;
+        assert!(unsupported.is_err_and(|e| e.is_not_supported()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_local_cache() -> Result<(), GitMoverError> {
+        let root = tempfile::tempdir()?;
+        let source = LocalPlatform::new(root.path().join("source"));
+        let destination = LocalPlatform::new(root.path().join("destination"));
+        source.check_git_access().await?;
+        destination.check_git_access().await?;
+        let repo = Repo {
+            name: "repo".to_string(),
+            path: "repo".to_string(),
+            ..Default::default()
+        };
+        let first = local_repo_with_commit(&source, &repo, &[]).await?;
+        let source_repo = git2::Repository::open_bare(source.get_git_url("repo"))?;
+        source_repo.reference("refs/heads/old", first, false, "old")?;
+
+        let cache_dir = root.path().join("cache");
+        let options = SyncOptions {
+            cache_dir: Some(cache_dir.clone()),
+            ..Default::default()
+        };
+        let source: Arc<Box<dyn Platform>> = Arc::new(Box::new(source));
+        let destination_platform: Arc<Box<dyn Platform>> = Arc::new(Box::new(destination.clone()));
+        let pb = ProgressBar::hidden();
+        sync_one_repo(
+            source.clone(),
+            destination_platform.clone(),
+            repo.clone(),
+            cache_dir.clone(),
+            options.clone(),
+            (0, &pb),
+        )
+        .await?;
+        let mirror_path = cache_dir.join(mirror_name(&source.get_git_url("repo")));
+        assert!(mirror_path.exists());
+
+        let second = commit_files(&source.get_git_url("repo"), &[], "second")?;
+        source_repo.find_reference("refs/heads/old")?.delete()?;
+        sync_one_repo(
+            source,
+            destination_platform,
+            repo,
+            cache_dir,
+            options,
+            (0, &pb),
+        )
+        .await?;
+
+        let mirror = git2::Repository::open_bare(mirror_path)?;
+        assert_eq!(
+            mirror.find_reference("refs/heads/main")?.target(),
+            Some(second)
+        );
+        assert!(mirror.find_reference("refs/heads/old").is_err());
+        let destination_repo = git2::Repository::open_bare(destination.get_git_url("repo"))?;
+        assert_eq!(
+            destination_repo.find_reference("refs/heads/main")?.target(),
+            Some(second)
+        );
+        Ok(())
+    }
 
     #[test]
     #[ignore] // This test is ignored because it requires a valid ssh key
@@ -0,0 +1,46 @@
+//! Fixtures shared by the tests
+use crate::{
+    errors::GitMoverError, local::platform::LocalPlatform, platform::Platform, utils::Repo,
+};
+
+/// Commit files on `main` of a bare repository, after its last commit,
+/// `main` becoming the default branch
+pub(crate) fn commit_files(
+    git_url: &str,
+    files: &[(&str, &str)],
+    message: &str,
+) -> Result<git2::Oid, GitMoverError> {
+    let repo = git2::Repository::open_bare(git_url)?;
+    let mut builder = repo.treebuilder(None)?;
+    for (name, content) in files {
+        builder.insert(name, repo.blob(content.as_bytes())?, 0o100644)?;
+    }
+    let tree = repo.find_tree(builder.write()?)?;
+    let signature = git2::Signature::now("git-mover", "git-mover@example.com")?;
+    let parent = match repo.find_reference("refs/heads/main") {
+        Ok(reference) => Some(reference.peel_to_commit()?),
+        Err(_) => None,
+    };
+    let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+    let commit = repo.commit(
+        Some("refs/heads/main"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )?;
+    repo.set_head("refs/heads/main")?;
+    Ok(commit)
+}
+
+/// Create a repository on a local platform with a first commit of files on `main`
+pub(crate) async fn local_repo_with_commit(
+    platform: &LocalPlatform,
+    repo: &Repo,
+    files: &[(&str, &str)],
+) -> Result<git2::Oid, GitMoverError> {
+    platform.check_git_access().await?;
+    platform.create_repo(repo.clone()).await?;
+    commit_files(&platform.get_git_url(&repo.path), files, "initial commit")
+}
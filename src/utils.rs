@@ -1,5 +1,4 @@
 //! Utility functions
-use std::collections::HashSet;
 use std::{fmt::Debug, sync::Arc};
 
 use serde::{Deserialize, Serialize};
@@ -7,13 +6,25 @@ use std::process::Stdio;
 use tokio::join;
 use tokio::process::Command;
 use tokio::time::{timeout, Duration};
+use url::Url;
 
+use crate::cli::DeletePolicy;
+use crate::diff::{diff_repos, format_plan};
 use crate::errors::GitMoverError;
 use crate::platform::{Platform, PlatformType};
-use crate::sync::{delete_repos, sync_repos};
+use crate::sync::{delete_repos, sync_repos, SyncOptions};
 use crate::{
-    codeberg::config::CodebergConfig, config::GitMoverConfig, github::config::GithubConfig,
+    azure::config::AzureConfig,
+    bitbucket::config::BitbucketConfig,
+    bitbucket_server::config::BitbucketServerConfig,
+    codeberg::config::CodebergConfig,
+    config::GitMoverConfig,
+    gitea::config::GiteaConfig,
+    github::config::{GithubConfig, GithubEnterpriseConfig},
     gitlab::config::GitlabConfig,
+    local::config::LocalConfig,
+    sourcehut::config::SourcehutConfig,
+    ssh::config::SshConfig,
 };
 
 /// Repository information
@@ -33,6 +44,38 @@ pub struct Repo {
 
     /// Whether the repository is a fork
     pub fork: bool,
+
+    /// Path on platforms without nested namespaces, if mapped (e.g. a prefixed Gitlab group project)
+    #[serde(default)]
+    pub mapped_path: Option<String>,
+
+    /// Topics of the repository
+    #[serde(default)]
+    pub topics: Vec<String>,
+
+    /// Homepage of the repository (empty if none)
+    #[serde(default)]
+    pub homepage: String,
+
+    /// Default branch of the repository (empty if unknown)
+    #[serde(default)]
+    pub default_branch: String,
+
+    /// Whether the repository is archived
+    #[serde(default)]
+    pub archived: bool,
+
+    /// Whether the issues are enabled (`None` if unknown)
+    #[serde(default)]
+    pub has_issues: Option<bool>,
+
+    /// Whether the wiki is enabled (`None` if unknown)
+    #[serde(default)]
+    pub has_wiki: Option<bool>,
+
+    /// Whether the projects are enabled (`None` if unknown)
+    #[serde(default)]
+    pub has_projects: Option<bool>,
 }
 
 impl Repo {
@@ -45,6 +88,25 @@ impl Repo {
         };
         format!("{}{}", self.name, fmt_path)
     }
+
+    /// Topics of the repo in the format accepted by most platforms
+    /// (lowercase, with dashes instead of spaces)
+    pub fn normalized_topics(&self) -> Vec<String> {
+        self.topics
+            .iter()
+            .map(|topic| topic.trim().to_lowercase().replace(' ', "-"))
+            .collect()
+    }
+
+    /// Path of the repo on platforms without nested namespaces
+    /// (the mapped path, or the name replacing a nested path like `sub/project`)
+    pub fn flat_path(&self) -> String {
+        match &self.mapped_path {
+            Some(mapped_path) => mapped_path.clone(),
+            None if self.path.contains('/') => self.name.clone(),
+            None => self.path.clone(),
+        }
+    }
 }
 
 /// GIT direction
@@ -67,6 +129,24 @@ pub fn input_number() -> Result<usize, GitMoverError> {
     }
 }
 
+/// Normalize an instance url: add the `https://` scheme if missing and remove the trailing slash
+pub(crate) fn normalize_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!("https://{url}")
+    }
+}
+
+/// Get the host of an instance url
+pub(crate) fn get_host(url: &str) -> String {
+    match Url::parse(&normalize_url(url)) {
+        Ok(parsed) => parsed.host_str().unwrap_or(url).to_string(),
+        Err(_) => url.to_string(),
+    }
+}
+
 /// check git access
 pub(crate) async fn check_ssh_access<S: AsRef<str>>(
     ssh_url: S,
@@ -106,6 +186,13 @@ pub(crate) fn get_plateform(
     };
     let chosen_platform = match plateform_from_cli {
         Some(platform) => platform,
+        None if config.cli_args.yes => {
+            return Err(match direction {
+                Direction::Source => "Missing the source platform (--source)",
+                Direction::Destination => "Missing the destination platform (--destination)",
+            }
+            .into());
+        }
         None => {
             println!(
                 "Choose a platform {}",
@@ -116,8 +203,16 @@ pub(crate) fn get_plateform(
             );
             let platforms = [
                 PlatformType::Github,
+                PlatformType::GithubEnterprise,
                 PlatformType::Gitlab,
                 PlatformType::Codeberg,
+                PlatformType::Gitea,
+                PlatformType::Bitbucket,
+                PlatformType::BitbucketServer,
+                PlatformType::Azure,
+                PlatformType::Sourcehut,
+                PlatformType::Local,
+                PlatformType::Ssh,
             ];
             for (i, platform) in platforms.iter().enumerate() {
                 println!("{i}: {platform}");
@@ -137,7 +232,15 @@ pub(crate) fn get_plateform(
     let plateform: Box<dyn Platform> = match chosen_platform {
         PlatformType::Gitlab => Box::new(GitlabConfig::get_plateform(config)?),
         PlatformType::Github => Box::new(GithubConfig::get_plateform(config)?),
+        PlatformType::GithubEnterprise => Box::new(GithubEnterpriseConfig::get_plateform(config)?),
         PlatformType::Codeberg => Box::new(CodebergConfig::get_plateform(config)?),
+        PlatformType::Gitea => Box::new(GiteaConfig::get_plateform(config)?),
+        PlatformType::Bitbucket => Box::new(BitbucketConfig::get_plateform(config)?),
+        PlatformType::BitbucketServer => Box::new(BitbucketServerConfig::get_plateform(config)?),
+        PlatformType::Azure => Box::new(AzureConfig::get_plateform(config)?),
+        PlatformType::Sourcehut => Box::new(SourcehutConfig::get_plateform(config)?),
+        PlatformType::Local => Box::new(LocalConfig::get_plateform(config)?),
+        PlatformType::Ssh => Box::new(SshConfig::get_plateform(config)?),
     };
     Ok(plateform)
 }
@@ -147,6 +250,12 @@ pub(crate) fn get_plateform(
 /// Error if an error happens
 pub async fn main_sync(config: GitMoverConfig) -> Result<(), GitMoverError> {
     let mut config = config;
+    if config.cli_args.yes && config.cli_args.delete_policy() == DeletePolicy::Prompt {
+        return Err("--delete=prompt can't be used in non-interactive mode".into());
+    }
+    if config.cli_args.yes && config.cli_args.private.is_none() {
+        return Err("Missing the policy of the private repos (--private=sync|skip)".into());
+    }
     let source_platform = get_plateform(&mut config, Direction::Source)?;
     println!("Chosen {} as source", source_platform.get_remote_url());
 
@@ -178,8 +287,22 @@ pub async fn main_sync(config: GitMoverConfig) -> Result<(), GitMoverError> {
         }
         Err(e) => return Err(e),
     }
-    let source_platform = Arc::new(source_platform);
-    let destination_platform = Arc::new(destination_platform);
+    sync_platforms(
+        &config,
+        Arc::new(source_platform),
+        Arc::new(destination_platform),
+    )
+    .await
+}
+
+/// Compare the repositories of the platforms, then sync, edit and delete them
+/// # Errors
+/// Error if the repositories can't be listed or a sync fails
+async fn sync_platforms(
+    config: &GitMoverConfig,
+    source_platform: Arc<Box<dyn Platform>>,
+    destination_platform: Arc<Box<dyn Platform>>,
+) -> Result<(), GitMoverError> {
     let (repos_source, repos_destination) = join!(
         source_platform.get_all_repos(),
         destination_platform.get_all_repos()
@@ -222,28 +345,49 @@ pub async fn main_sync(config: GitMoverConfig) -> Result<(), GitMoverError> {
         "Number of repos in destination: {}",
         repos_destination.len()
     );
-    let cloned_repos_source_without_fork = repos_source_without_fork.clone();
-    let cloned_repos_destination = repos_destination.clone();
-    let item_source_set: HashSet<_> = cloned_repos_source_without_fork.iter().collect();
-    let item_destination_set: HashSet<_> = repos_destination.iter().collect();
-    let missing_dest: Vec<Repo> = cloned_repos_destination
-        .into_iter()
-        .filter(|item| !item_source_set.contains(item))
-        .collect();
+    println!("Comparing the repositories...");
+    let diff = diff_repos(
+        source_platform.clone(),
+        destination_platform.clone(),
+        repos_source,
+        repos_destination,
+        SyncOptions::from(config).refs,
+    )
+    .await;
+    let missing_dest = diff.to_delete;
     let resync = config.cli_args.resync;
-    let difference: Vec<Repo> = if resync {
-        cloned_repos_source_without_fork
+    let (difference, forks_to_sync, to_edit) = if resync {
+        (
+            repos_source_without_fork,
+            repos_source_forks.clone(),
+            vec![],
+        )
     } else {
-        cloned_repos_source_without_fork
-            .into_iter()
-            .filter(|item| !item_destination_set.contains(item))
-            .collect()
+        let (forks, difference): (Vec<Repo>, Vec<Repo>) =
+            diff.to_sync.into_iter().partition(|repo| repo.fork);
+        (difference, forks, diff.to_edit)
     };
+    println!("Number of repos up to date: {}", diff.up_to_date);
     println!("Number of repos to sync: {}", difference.len());
+    println!("Number of repos to edit: {}", to_edit.len());
     println!("Number of repos to delete: {}", missing_dest.len());
-    if !difference.is_empty() && yes_no_input("Do you want to start syncing ? (y/n)")? {
+    if config.cli_args.dry_run {
+        let no_forks = config.cli_args.no_forks;
+        let no_delete = config.cli_args.delete_policy() == DeletePolicy::Never;
+        let plan = format_plan(
+            &diff.changes,
+            &difference,
+            (!no_forks).then_some(forks_to_sync.as_slice()),
+            &to_edit,
+            (!no_delete).then_some(missing_dest.as_slice()),
+            config.cli_args.private,
+        );
+        println!("{plan}");
+        return Ok(());
+    }
+    if !difference.is_empty() && confirm(config, "Do you want to start syncing ? (y/n)")? {
         match sync_repos(
-            &config,
+            config,
             source_platform.clone(),
             destination_platform.clone(),
             difference,
@@ -260,15 +404,17 @@ pub async fn main_sync(config: GitMoverConfig) -> Result<(), GitMoverError> {
         println!("Not syncing forks");
     } else if repos_source_forks.is_empty() {
         println!("No forks found");
-    } else if yes_no_input(format!(
-        "Do you want to sync forks ({})? (y/n)",
-        repos_source_forks.len()
-    ))? {
+    } else if forks_to_sync.is_empty() {
+        println!("All forks are up to date");
+    } else if confirm(
+        config,
+        format!("Do you want to sync forks ({})? (y/n)", forks_to_sync.len()),
+    )? {
         match sync_repos(
-            &config,
+            config,
             source_platform,
             destination_platform.clone(),
-            repos_source_forks,
+            forks_to_sync,
         )
         .await
         {
@@ -280,15 +426,43 @@ pub async fn main_sync(config: GitMoverConfig) -> Result<(), GitMoverError> {
             }
         }
     }
-    if config.cli_args.no_delete {
-        println!("Not prompting for deletion");
+    if !to_edit.is_empty()
+        && confirm(
+            config,
+            format!(
+                "Do you want to update the metadata of the edited ({}) repos? (y/n)",
+                to_edit.len()
+            ),
+        )?
+    {
+        for repo in to_edit {
+            let edited_repo = Repo {
+                path: repo.flat_path(),
+                ..repo
+            };
+            match destination_platform.edit_repo(edited_repo.clone()).await {
+                Ok(_) => {
+                    println!("Updated {}", edited_repo.show_full_name());
+                }
+                Err(e) => {
+                    println!("Error: {e}");
+                }
+            }
+        }
+    }
+    let delete_policy = config.cli_args.delete_policy();
+    if delete_policy == DeletePolicy::Never {
+        println!("Not deleting the missing repos");
     } else if missing_dest.is_empty() {
         println!("Nothing to delete");
-    } else if yes_no_input(format!(
-        "Do you want to delete the missing ({}) repos (manually)? (y/n)",
-        missing_dest.len()
-    ))? {
-        match delete_repos(destination_platform, missing_dest).await {
+    } else if delete_policy == DeletePolicy::Always
+        || yes_no_input(format!(
+            "Do you want to delete the missing ({}) repos (manually)? (y/n)",
+            missing_dest.len()
+        ))?
+    {
+        let ask = delete_policy == DeletePolicy::Prompt;
+        match delete_repos(destination_platform, missing_dest, ask).await {
             Ok(_) => {
                 println!("All repos deleted");
             }
@@ -317,6 +491,18 @@ pub(crate) fn input() -> Result<String, GitMoverError> {
     Ok(s)
 }
 
+/// Get a yes/no input from the user, answering yes without asking in non-interactive mode
+pub(crate) fn confirm<S: AsRef<str>>(
+    config: &GitMoverConfig,
+    msg: S,
+) -> Result<bool, GitMoverError> {
+    if config.cli_args.yes {
+        println!("{} y", msg.as_ref());
+        return Ok(true);
+    }
+    yes_no_input(msg)
+}
+
 /// Get a yes/no input from the user
 pub(crate) fn yes_no_input<S: AsRef<str>>(msg: S) -> Result<bool, GitMoverError> {
     let msg = msg.as_ref();
@@ -350,6 +536,7 @@ mod test {
             description: "test".to_string(),
             private: false,
             fork: false,
+            ..Default::default()
         };
         let repo2 = Repo {
             name: "test".to_string(),
@@ -357,6 +544,7 @@ mod test {
             description: "test".to_string(),
             private: false,
             fork: false,
+            ..Default::default()
         };
         let repo3 = Repo {
             name: "test".to_string(),
@@ -364,9 +552,139 @@ mod test {
             description: "test".to_string(),
             private: true,
             fork: false,
+            ..Default::default()
         };
         assert!(repo1 == repo2);
         assert!(repo1 != repo3);
         assert_eq!(repo1, repo2);
     }
+
+    #[test]
+    fn instance_url() {
+        assert_eq!(normalize_url("git.example.com/"), "https://git.example.com");
+        assert_eq!(
+            normalize_url("http://localhost:3000/gitea"),
+            "http://localhost:3000/gitea"
+        );
+        assert_eq!(get_host("https://git.example.com/gitea"), "git.example.com");
+        assert_eq!(get_host("codeberg.org"), "codeberg.org");
+    }
+
+    #[test]
+    fn non_interactive() {
+        let mut config = GitMoverConfig {
+            cli_args: crate::cli::GitMoverCli {
+                yes: true,
+                destination: Some(PlatformType::Local),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(get_plateform(&mut config, Direction::Source).is_err());
+        let error = get_plateform(&mut config, Direction::Destination)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        assert!(error.contains("`path` of `[local]`"));
+        assert!(confirm(&config, "Continue? (y/n)").unwrap_or_default());
+        assert_eq!(config.cli_args.delete_policy(), DeletePolicy::Never);
+        config.cli_args.delete = Some(DeletePolicy::Always);
+        assert_eq!(config.cli_args.delete_policy(), DeletePolicy::Always);
+        config.cli_args.no_delete = true;
+        assert_eq!(config.cli_args.delete_policy(), DeletePolicy::Never);
+    }
+
+    #[test]
+    fn github_enterprise() -> Result<(), GitMoverError> {
+        let mut config = GitMoverConfig::default();
+        config.config_data.github = Some(GithubConfig {
+            username: Some("user".to_string()),
+            token: Some("token".to_string()),
+            owner: None,
+        });
+        config.config_data.github_enterprise = Some(GithubEnterpriseConfig {
+            url: Some("github.example.com".to_string()),
+            username: Some("user".to_string()),
+            token: Some("token".to_string()),
+            owner: None,
+        });
+        config.cli_args.source = Some(PlatformType::Github);
+        config.cli_args.destination = Some(PlatformType::GithubEnterprise);
+        let source = get_plateform(&mut config, Direction::Source)?;
+        let destination = get_plateform(&mut config, Direction::Destination)?;
+        assert_eq!(source.get_remote_url(), "github.com");
+        assert_eq!(destination.get_remote_url(), "github.example.com");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn non_interactive_policies() {
+        let cli_args = crate::cli::GitMoverCli {
+            yes: true,
+            ..Default::default()
+        };
+        let config = GitMoverConfig {
+            cli_args: cli_args.clone(),
+            ..Default::default()
+        };
+        let error = main_sync(config).await.err().map(|e| e.to_string());
+        assert!(error.unwrap_or_default().contains("--private"));
+        let config = GitMoverConfig {
+            cli_args: crate::cli::GitMoverCli {
+                private: Some(crate::cli::PrivatePolicy::Skip),
+                delete: Some(DeletePolicy::Prompt),
+                ..cli_args
+            },
+            ..Default::default()
+        };
+        let error = main_sync(config).await.err().map(|e| e.to_string());
+        assert!(error.unwrap_or_default().contains("--delete=prompt"));
+    }
+
+    #[tokio::test]
+    async fn dry_run() -> Result<(), GitMoverError> {
+        use crate::local::platform::LocalPlatform;
+        use crate::test_utils::local_repo_with_commit;
+        let root = tempfile::tempdir()?;
+        let source = LocalPlatform::new(root.path().join("source"));
+        let destination = LocalPlatform::new(root.path().join("destination"));
+        let repo = |name: &str, description: &str, private: bool| Repo {
+            name: name.to_string(),
+            path: name.to_string(),
+            description: description.to_string(),
+            private,
+            ..Default::default()
+        };
+        source.create_repo(repo("new", "", false)).await?;
+        source.create_repo(repo("secret", "", true)).await?;
+        source.create_repo(repo("edited", "new", false)).await?;
+        local_repo_with_commit(&source, &repo("pushed", "", false), &[]).await?;
+        for name in ["edited", "pushed", "deleted"] {
+            destination.create_repo(repo(name, "", false)).await?;
+        }
+        let destination_repos = destination.get_all_repos().await?;
+
+        let config = GitMoverConfig {
+            cli_args: crate::cli::GitMoverCli {
+                yes: true,
+                dry_run: true,
+                private: Some(crate::cli::PrivatePolicy::Sync),
+                delete: Some(DeletePolicy::Always),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        sync_platforms(
+            &config,
+            Arc::new(Box::new(source)),
+            Arc::new(Box::new(destination.clone())),
+        )
+        .await?;
+
+        // nothing created, pushed, edited or deleted
+        assert_eq!(destination.get_all_repos().await?, destination_repos);
+        let pushed = git2::Repository::open_bare(destination.get_git_url("pushed"))?;
+        assert!(pushed.find_reference("refs/heads/main").is_err());
+        Ok(())
+    }
 }